@@ -0,0 +1,17 @@
+use enum_dispatch::enum_dispatch;
+
+use crate::hittables::objects::sphere::Sphere;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+
+// What rays that leave the scene see, looked up by their direction.
+#[enum_dispatch(BgEnum)]
+pub trait Background {
+    fn value(&self, direction: &Vec3) -> Color;
+}
+
+pub fn direction_uv(direction: &Vec3) -> (f64, f64) {
+    // Equirectangular texture coordinates of a direction, laid out like a sphere's so an image
+    // wrapped on a sphere looks the same from inside it.
+    Sphere::get_sphere_uv(&Vec3::unit_vector(direction))
+}
@@ -0,0 +1,28 @@
+use crate::backgrounds::background::Background;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+
+// Blends vertically between two colors, from straight down to straight up.
+#[derive(Clone)]
+pub struct GradientBackground {
+    bottom: Color,
+    top: Color,
+}
+
+impl GradientBackground {
+    pub fn from(bottom: Color, top: Color) -> GradientBackground {
+        GradientBackground { bottom, top }
+    }
+
+    pub fn sky() -> GradientBackground {
+        // The white to light blue sky of the first book.
+        GradientBackground::from(Color::from(1.0, 1.0, 1.0), Color::from(0.5, 0.7, 1.0))
+    }
+}
+
+impl Background for GradientBackground {
+    fn value(&self, direction: &Vec3) -> Color {
+        let a = 0.5 * (Vec3::unit_vector(direction).y() + 1.0);
+        &(&self.bottom * (1.0 - a)) + &(&self.top * a)
+    }
+}
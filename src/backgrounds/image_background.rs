@@ -0,0 +1,27 @@
+use crate::backgrounds::background::{direction_uv, Background};
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::texture::Texture;
+
+// An equirectangular (latitude-longitude) panorama surrounding the scene. For HDR images that
+// should also be importance sampled as a light, use an EnvironmentLight instead.
+#[derive(Clone)]
+pub struct ImageBackground {
+    image: ImageTexture,
+}
+
+impl ImageBackground {
+    pub fn from(filename: &str) -> ImageBackground {
+        ImageBackground {
+            image: ImageTexture::from(filename),
+        }
+    }
+}
+
+impl Background for ImageBackground {
+    fn value(&self, direction: &Vec3) -> Color {
+        let (u, v) = direction_uv(direction);
+        self.image.value(u, v, direction)
+    }
+}
@@ -0,0 +1,22 @@
+//noinspection RsUnusedImport
+use crate::math_structures::color::Color; //Required
+use enum_dispatch::enum_dispatch;
+
+use crate::backgrounds::background::Background;
+
+pub mod background;
+pub mod gradient_background;
+pub mod image_background;
+pub mod solid_background;
+pub mod texture_background;
+//noinspection RsUnusedImport
+use crate::math_structures::vec3::Vec3; //Required
+
+#[derive(Clone)]
+#[enum_dispatch]
+pub enum BgEnum {
+    Gradient(gradient_background::GradientBackground),
+    Image(image_background::ImageBackground),
+    Solid(solid_background::SolidBackground),
+    Texture(texture_background::TextureBackground),
+}
@@ -0,0 +1,20 @@
+use crate::backgrounds::background::Background;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+
+#[derive(Clone)]
+pub struct SolidBackground {
+    color: Color,
+}
+
+impl SolidBackground {
+    pub fn from(color: Color) -> SolidBackground {
+        SolidBackground { color }
+    }
+}
+
+impl Background for SolidBackground {
+    fn value(&self, _direction: &Vec3) -> Color {
+        self.color
+    }
+}
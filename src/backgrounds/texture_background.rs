@@ -0,0 +1,27 @@
+use crate::backgrounds::background::{direction_uv, Background};
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+use crate::textures::texture::Texture;
+use crate::textures::TexEnum;
+
+// Any texture stretched around the scene. It is looked up at the equirectangular coordinates
+// of the direction, with the unit direction itself as the point for solid textures.
+#[derive(Clone)]
+pub struct TextureBackground {
+    texture: Box<TexEnum>,
+}
+
+impl TextureBackground {
+    pub fn from(texture: TexEnum) -> TextureBackground {
+        TextureBackground {
+            texture: Box::new(texture),
+        }
+    }
+}
+
+impl Background for TextureBackground {
+    fn value(&self, direction: &Vec3) -> Color {
+        let (u, v) = direction_uv(direction);
+        self.texture.value(u, v, &Vec3::unit_vector(direction))
+    }
+}
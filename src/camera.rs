@@ -2,17 +2,28 @@ use std::fs::File;
 use std::io;
 use std::io::Write;
 
-use crate::hittables::hittable::Hittable;
+use crate::backgrounds::background::Background;
+use crate::backgrounds::solid_background::SolidBackground;
+use crate::backgrounds::BgEnum;
+use crate::hittables::hittable::{HitRecord, Hittable};
 use crate::hittables::hittable_list::HittableList;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::dielectric::{current_medium, Medium};
 use crate::materials::material::Material;
+use crate::materials::MatEnum;
 use crate::math_structures::color::foo::fmt_to_file;
-use crate::math_structures::color::{write_color, Color};
+use crate::math_structures::color::{write_color, Color, WhiteBalance};
 use crate::math_structures::interval::Interval;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{random_in_unit_disk, Point3, Vec3};
 use crate::pdf::hittable_pdf::HittablePdf;
 use crate::pdf::mixture_pdf::MixturePdf;
+use crate::pdf::pdf::Pdf;
 use crate::rtweekend::{degrees_to_radians, random_double, INFINITY};
+use std::sync::Arc;
+
+// Longest random walk followed through a scattering medium before the path is given up on.
+const MAX_SCATTERING_STEPS: i64 = 1024;
 
 #[derive(Clone)]
 pub struct Camera {
@@ -37,15 +48,26 @@ pub struct Camera {
     // Defocus disk vertical radius
     pub defocus_disk_v: Vec3,
 
-    // Scene background color
-    background: Color,
+    // Scene background, seen by rays that escape the scene
+    pub background: BgEnum,
     pub(crate) sqrt_spp: f64,
     recip_sqrt_spp: f64,
+
+    // Add every emitter in the world to the lights list before rendering
+    pub auto_lights: bool,
+    // Image based light seen by escaped rays in place of the background color
+    pub environment: Option<Arc<EnvironmentLight>>,
+    // Whether camera rays see the background; it lights the scene either way
+    pub background_visible: bool,
+    // Color temperature correction applied to every pixel
+    pub white_balance: Option<WhiteBalance>,
 }
 
 impl Camera {
     #[allow(dead_code)] //Using in single thread render
     pub fn render(&self, mut out_file: &mut File, world: &HittableList, lights: &HittableList) {
+        let lights = &self.scene_lights(world, lights);
+
         // Render
         fmt_to_file!(
             &mut out_file,
@@ -66,10 +88,13 @@ impl Camera {
                     for s_j in 0..self.sqrt_spp as i64 {
                         for s_i in 0..self.sqrt_spp as i64 {
                             let r = self.get_ray(i, j, s_i, s_j);
-                            pixel_color += &self.ray_color(&r, self.max_depth, world, lights);
+                            pixel_color += &self.ray_color(&r, self.max_depth, world, lights, &[]);
                         }
                     }
                 }
+                if let Some(wb) = &self.white_balance {
+                    pixel_color = wb.apply(&pixel_color);
+                }
                 write_color(&mut out_file, &pixel_color, self.samples_per_pixel);
             }
         }
@@ -138,10 +163,30 @@ impl Camera {
             defocus_angle,
             defocus_disk_u,
             defocus_disk_v,
-            background,
+            background: BgEnum::Solid(SolidBackground::from(background)),
             sqrt_spp,
             recip_sqrt_spp,
+            auto_lights: true,
+            environment: None,
+            background_visible: true,
+            white_balance: None,
+        }
+    }
+
+    pub fn scene_lights(&self, world: &HittableList, lights: &HittableList) -> HittableList {
+        // The lights to sample while rendering: the scene's list, every emitter in the world
+        // when `auto_lights` is on, and the environment light if there is one.
+        let mut all = if self.auto_lights {
+            lights.with_discovered_lights(world)
+        } else {
+            lights.clone()
+        };
+        if let Some(env) = &self.environment {
+            all.add(env.clone());
         }
+        // Built once here, so the copies each thread renders with share it.
+        all.light_table();
+        all
     }
     //    ray get_ray(int i, int j, int s_i, int s_j) const {
     pub(crate) fn get_ray(&self, i: i64, j: i64, s_i: i64, s_j: i64) -> Ray {
@@ -182,22 +227,107 @@ impl Camera {
         depth: i64,
         world: &HittableList,
         lights: &HittableList,
+        media: &[Medium],
     ) -> Color {
         // If we've exceeded the ray bounce limit, no more light is gathered.
         if depth <= 0 {
             return Color::from(0.0, 0.0, 0.0);
         }
 
-        let rec;
-        // If the ray hits nothing, return the background color.
-        match world.hit(r, &Interval::from(0.001, INFINITY)) {
-            None => {
-                return self.background;
+        // Inside a medium that scatters light, the ray takes a random walk between particles
+        // until it reaches a surface. The walk is followed in a loop rather than by recursing,
+        // since in dense media such as milk it can take hundreds of steps.
+        let medium = current_medium(media);
+        let mut r = Ray::from_set_time(*r.origin(), *r.direction(), r.time());
+        let mut throughput = Color::from(1.0, 1.0, 1.0);
+        for _ in 0..MAX_SCATTERING_STEPS {
+            let rec;
+            // If the ray hits nothing, return the background.
+            match world.hit(&r, &Interval::from(0.001, INFINITY)) {
+                None => {
+                    return &throughput * &self.background_color(&r, depth);
+                }
+                Some(x) => {
+                    rec = x;
+                }
             }
-            Some(x) => {
-                rec = x;
+
+            // Light fades on its way through whatever medium the ray is travelling in.
+            let m = match medium {
+                None => return self.surface_color(&r, rec, depth, world, lights, media),
+                Some(m) => m,
+            };
+            let distance = rec.t * r.direction().length();
+            let (scattered_at, weight) = m.sample_scattering(distance, &throughput);
+            throughput = &throughput * &weight;
+            if throughput.length_squared() <= 0.0 {
+                return Color::from(0.0, 0.0, 0.0);
+            }
+            match scattered_at {
+                None => {
+                    let color = self.surface_color(&r, rec, depth, world, lights, media);
+                    return &throughput * &color;
+                }
+                Some(t) => {
+                    let p = r.at(t / r.direction().length());
+                    r = Ray::from_set_time(p, m.phase(r.direction()).generate(), r.time());
+                }
             }
         }
+        Color::from(0.0, 0.0, 0.0)
+    }
+
+    fn surface_color(
+        &self,
+        r: &Ray,
+        mut rec: HitRecord,
+        depth: i64,
+        world: &HittableList,
+        lights: &HittableList,
+        media: &[Medium],
+    ) -> Color {
+        // A mix acts as whichever material it picks for this path, emission included, so glass
+        // in it still bounds a medium.
+        if let MatEnum::MixMaterial(m) = &rec.mat {
+            rec.mat = m.choose(r, &rec).clone();
+        }
+
+        // Dielectrics are boundaries between media. Work out which media lie beyond the surface
+        // and what index of refraction lies outside it, given the media the ray is in.
+        let mut beyond = None;
+        let mut surrounded = None;
+        if let MatEnum::Dielectric(d) = &rec.mat {
+            let medium = d.medium();
+            let mut others = media.to_vec();
+            if !rec.front_face {
+                if let Some(i) = others.iter().rposition(|m| m.same_as(&medium)) {
+                    others.remove(i);
+                }
+            }
+            let outside = current_medium(&others).cloned();
+            if rec.front_face {
+                others.push(medium.clone());
+            }
+            if let Some(o) = outside {
+                if o.priority > medium.priority {
+                    // The surface is inside a medium of higher priority, which fills the
+                    // overlap, so light passes straight through it.
+                    let through = Ray::from_set_time(rec.p, *r.direction(), r.time());
+                    return self.ray_color(&through, depth, world, lights, &others);
+                }
+                surrounded = Some(d.surrounded_by(o.ir));
+            }
+            beyond = Some(others);
+        }
+        if let Some(d) = surrounded {
+            rec.mat = MatEnum::Dielectric(d);
+        }
+        let media_along = |direction: &Vec3| -> Vec<Medium> {
+            match &beyond {
+                Some(b) if Vec3::dot(direction, &rec.normal) < 0.0 => b.clone(),
+                _ => media.to_vec(),
+            }
+        };
 
         let srec;
         let color_from_emission = rec.mat.emitted(r, &rec, rec.u, rec.v, &rec.p);
@@ -211,12 +341,17 @@ impl Camera {
         }
 
         if srec.skip_pdf {
+            let next_media = media_along(srec.skip_pdf_ray.direction());
             return &srec.attenuation
-                * &self.ray_color(&srec.skip_pdf_ray, depth - 1, world, lights);
+                * &self.ray_color(&srec.skip_pdf_ray, depth - 1, world, lights, &next_media);
         }
 
-        let light_ptr = HittablePdf::from(lights.clone(), rec.p);
-        let p = if lights.objects.len() != 0 {
+        // Delta lights can't be hit by the scattered ray, so their light is gathered here.
+        let color_from_delta_lights =
+            self.delta_light_color(r, &rec, &srec.attenuation, world, lights);
+
+        let light_ptr = HittablePdf::from(lights, rec.p, r.time());
+        let p = if lights.has_sampled_lights() {
             Box::new(MixturePdf::from(Box::new(light_ptr), srec.pdf_ptr))
         } else {
             srec.pdf_ptr
@@ -224,12 +359,63 @@ impl Camera {
 
         let scattered = Ray::from_set_time(rec.p, p.generate(), r.time());
         let pdf_val = p.value(scattered.direction());
+        if pdf_val <= 0.0 {
+            // Sampled below the surface, where the material reflects nothing.
+            return &color_from_emission + &color_from_delta_lights;
+        }
+
+        let scattering_color = rec
+            .mat
+            .scattering_color(r, &rec, &srec.attenuation, &scattered);
+
+        let next_media = media_along(scattered.direction());
+        let sample_color = self.ray_color(&scattered, depth - 1, world, lights, &next_media);
+        let color_from_scatter = &(&scattering_color * &sample_color) / pdf_val;
 
-        let scattering_pdf = rec.mat.scattering_pdf(r, &rec, &scattered);
+        &(&color_from_emission + &color_from_delta_lights) + &color_from_scatter
+    }
 
-        let sample_color = self.ray_color(&scattered, depth - 1, world, lights);
-        let color_from_scatter = &(&(&srec.attenuation * scattering_pdf) * &sample_color) / pdf_val;
+    fn background_color(&self, r: &Ray, depth: i64) -> Color {
+        // The environment light takes the place of the background when there is one. A hidden
+        // background is black to rays straight from the camera only.
+        if !self.background_visible && depth == self.max_depth {
+            return Color::from(0.0, 0.0, 0.0);
+        }
+        match &self.environment {
+            None => self.background.value(r.direction()),
+            Some(env) => env.radiance(r.direction()),
+        }
+    }
 
-        &color_from_emission + &color_from_scatter
+    fn delta_light_color(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        world: &HittableList,
+        lights: &HittableList,
+    ) -> Color {
+        // Sum of the light each delta light delivers to the hit point, with a shadow ray to
+        // check that nothing is in the way.
+        let mut color = Color::from(0.0, 0.0, 0.0);
+        for light in lights.objects.iter().filter(|l| l.is_delta_light()) {
+            let ls = match light.illuminate(&rec.p, r.time()) {
+                None => continue,
+                Some(x) => x,
+            };
+            let shadow_ray = Ray::from_set_time(rec.p, ls.direction, r.time());
+            let scattering_color = rec.mat.scattering_color(r, rec, attenuation, &shadow_ray);
+            if scattering_color.length_squared() <= 0.0 {
+                continue;
+            }
+            if world
+                .hit(&shadow_ray, &Interval::from(0.001, ls.distance - 0.001))
+                .is_some()
+            {
+                continue;
+            }
+            color += &(&scattering_color * &ls.irradiance);
+        }
+        color
     }
 }
@@ -0,0 +1,32 @@
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::ray::Ray;
+use std::sync::Arc;
+
+// Renders the wrapped object as usual but hides its emitters from automatic light discovery,
+// e.g. for a dim glowing prop that is not worth spending light samples on.
+#[derive(Clone)]
+pub struct ExcludeFromLights {
+    object: Arc<dyn Hittable + Send + Sync>,
+}
+
+impl ExcludeFromLights {
+    pub fn from(object: Arc<dyn Hittable + Send + Sync>) -> ExcludeFromLights {
+        ExcludeFromLights { object }
+    }
+}
+
+impl Hittable for ExcludeFromLights {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        self.object.hit(r, ray_t)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.object.bounding_box()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+}
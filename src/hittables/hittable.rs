@@ -1,8 +1,13 @@
+use crate::materials::material::Material;
 use crate::materials::MatEnum;
 use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::{luminance, Color};
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{INFINITY, PI};
+use std::sync::Arc;
 
 pub struct HitRecord {
     pub p: Point3,
@@ -12,6 +17,8 @@ pub struct HitRecord {
     pub(crate) front_face: bool,
     pub u: f64,
     pub v: f64,
+    // Optional per-primitive color (e.g. particle colors) that tints the material.
+    pub color: Option<Color>,
 }
 
 impl HitRecord {
@@ -25,6 +32,22 @@ impl HitRecord {
             -&(outward_normal.clone())
         };
     }
+
+    pub fn tint(&self, c: Color) -> Color {
+        // Applies the per-primitive color, if any, to a material color.
+        match self.color {
+            None => c,
+            Some(x) => &c * &x,
+        }
+    }
+}
+
+// Light arriving at a point from a light that is only reached through shadow rays: the unit
+// direction towards it, how far away it is and the irradiance it delivers there.
+pub struct LightSample {
+    pub direction: Vec3,
+    pub distance: f64,
+    pub irradiance: Color,
 }
 
 pub trait Hittable {
@@ -34,12 +57,76 @@ pub trait Hittable {
 
     fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync>;
 
-    fn pdf_value(&self, _o: &Point3, _v: &Vec3) -> f64 {
+    fn pdf_value(&self, _o: &Point3, _v: &Vec3, _time: f64) -> f64 {
         0.0
     }
-    fn random(&self, _o: &Vec3) -> Vec3 {
+    fn random(&self, _o: &Vec3, _time: f64) -> Vec3 {
         Vec3::from(1.0, 0.0, 0.0)
     }
+
+    fn power(&self) -> f64 {
+        // Estimated emitted power, used to pick between lights. Zero when unknown.
+        0.0
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        // The emissive parts of the object that can be sampled as lights.
+        vec![]
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        // Directions light can leave the object in, used by the light hierarchy.
+        LightCone::all()
+    }
+
+    fn is_delta_light(&self) -> bool {
+        // Lights that no scattered ray can hit, e.g. point lights. They are left out of the
+        // light pdf and gathered with shadow rays through `illuminate` instead.
+        false
+    }
+
+    fn illuminate(&self, _p: &Point3, _time: f64) -> Option<LightSample> {
+        None
+    }
+}
+
+pub fn area_pdf_value(
+    o: &Point3,
+    v: &Vec3,
+    time: f64,
+    area: f64,
+    hit: impl Fn(&Ray, &Interval) -> Option<HitRecord>,
+) -> f64 {
+    // Pdf of the direction v for objects sampled by picking a point uniformly over their
+    // surface. The point may be any of the ones the direction passes through, not only the
+    // nearest, so all of them are added up. `hit` must give the surface's geometric normal
+    // rather than a smoothed one.
+    let r = Ray::from_set_time(*o, *v, time);
+    let mut pdf = 0.0;
+    let mut t_min = 0.001;
+    while let Some(rec) = hit(&r, &Interval::from(t_min, INFINITY)) {
+        let distance_squared = rec.t * rec.t * v.length_squared();
+        let cosine = f64::abs(Vec3::dot(v, &rec.normal) / v.length());
+        pdf += distance_squared / (cosine * area);
+        t_min = rec.t;
+    }
+    pdf
+}
+
+pub fn beam_cone(mat: &MatEnum, surface: LightCone) -> LightCone {
+    // The cone light leaves a surface in, narrowed to the material's beam when that is
+    // tighter.
+    match mat.emission_beam() {
+        Some((axis, spread)) if spread < f64::min(surface.theta_o + surface.theta_e, PI) => {
+            LightCone::from(axis, 0.0, spread)
+        }
+        _ => surface,
+    }
+}
+
+pub fn emitted_power(mat: &MatEnum, area: f64) -> f64 {
+    // Power leaving one side of a diffuse emitter with the material's typical radiance.
+    PI * area * luminance(&mat.emission_estimate())
 }
 
 impl Clone for Box<dyn Hittable + Send + Sync> {
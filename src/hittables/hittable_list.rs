@@ -1,14 +1,20 @@
 use crate::hittables::hittable::{HitRecord, Hittable};
 use crate::math_structures::aabb::Aabb;
+use crate::math_structures::alias_table::AliasTable;
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightBvh;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{Point3, Vec3};
-use crate::rtweekend::random_int_bounded;
-use std::sync::Arc;
+use crate::rtweekend::random_double;
+use std::sync::{Arc, OnceLock};
+
+const MAX_FLAT_LIGHTS: usize = 16;
 
 pub struct HittableList {
     pub objects: Vec<Arc<dyn Hittable + Send + Sync>>,
     bbox: Aabb,
+    // Built the first time the list is sampled as a set of lights.
+    light_table: OnceLock<AliasTable>,
 }
 
 impl HittableList {
@@ -16,12 +22,61 @@ impl HittableList {
         HittableList {
             objects: vec![],
             bbox: Aabb::blank(),
+            light_table: OnceLock::new(),
         }
     }
 
     pub(crate) fn add(&mut self, object: Arc<dyn Hittable + Send + Sync>) {
         self.bbox = Aabb::from_aabbs(&self.bbox, &object.bounding_box());
-        self.objects.push(object)
+        self.objects.push(object);
+        self.light_table = OnceLock::new();
+    }
+
+    pub fn with_discovered_lights(&self, world: &HittableList) -> HittableList {
+        // This list plus every emitter found in the world. Beyond a handful of emitters they
+        // are sampled through a light hierarchy instead of one by one.
+        let found = world.lights();
+        let mut lights = self.clone();
+        if found.len() > MAX_FLAT_LIGHTS {
+            let mut emitters = HittableList::blank();
+            for light in found {
+                emitters.add(light);
+            }
+            lights.add(Arc::new(LightBvh::from_list(&emitters)));
+        } else {
+            for light in found {
+                lights.add(light);
+            }
+        }
+        lights
+    }
+
+    pub fn has_sampled_lights(&self) -> bool {
+        // Whether any light here can be sampled by direction, i.e. is not a delta light.
+        self.objects.iter().any(|o| !o.is_delta_light())
+    }
+
+    pub(crate) fn light_table(&self) -> &AliasTable {
+        // Lights are picked in proportion to their estimated power. Objects whose power is
+        // unknown get the average of the others, so they are still sampled; if nothing is
+        // known every light is equally likely. Delta lights are never picked.
+        self.light_table.get_or_init(|| {
+            let mut powers: Vec<f64> = self.objects.iter().map(|o| o.power()).collect();
+            let known: Vec<f64> = powers.iter().copied().filter(|&p| p > 0.0).collect();
+            let average = if known.is_empty() {
+                1.0
+            } else {
+                known.iter().sum::<f64>() / known.len() as f64
+            };
+            for (p, object) in powers.iter_mut().zip(&self.objects) {
+                if object.is_delta_light() {
+                    *p = 0.0;
+                } else if *p <= 0.0 {
+                    *p = average;
+                }
+            }
+            AliasTable::from(&powers)
+        })
     }
 }
 
@@ -51,22 +106,33 @@ impl Hittable for HittableList {
         Box::from((*self).clone())
     }
 
-    fn pdf_value(&self, o: &Point3, v: &Vec3) -> f64 {
-        let weight = 1.0 / self.objects.len() as f64;
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        if self.objects.is_empty() {
+            return 0.0;
+        }
+        let table = self.light_table();
         let mut sum = 0.0;
 
-        for object in &self.objects {
-            sum += weight * object.pdf_value(o, v);
+        for (i, object) in self.objects.iter().enumerate() {
+            sum += table.pmf(i) * object.pdf_value(o, v, time);
         }
-        return sum;
+        sum
     }
 
-    fn random(&self, o: &Vec3) -> Vec3 {
-        if self.objects.len() == 0 {
+    fn random(&self, o: &Vec3, time: f64) -> Vec3 {
+        if self.objects.is_empty() {
             return Vec3::blank();
         }
-        let int_size = self.objects.len() as i64;
-        return self.objects[random_int_bounded(0, int_size - 1) as usize].random(o);
+        let i = self.light_table().sample(random_double());
+        self.objects[i].random(o, time)
+    }
+
+    fn power(&self) -> f64 {
+        self.objects.iter().map(|o| o.power()).sum()
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        self.objects.iter().flat_map(|o| o.lights()).collect()
     }
 }
 
@@ -77,6 +143,7 @@ impl Clone for HittableList {
             this.add(x.clone());
         }
         this.bbox = self.bbox.clone();
+        this.light_table = self.light_table.clone();
         this
     }
 }
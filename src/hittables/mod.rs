@@ -1,3 +1,4 @@
+pub mod exclude_from_lights;
 pub mod hittable;
 pub mod hittable_list;
 pub mod objects;
@@ -0,0 +1,270 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{emitted_power, HitRecord, Hittable};
+use crate::materials::material::Material;
+use crate::materials::MatEnum;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, INFINITY};
+
+#[derive(Clone)]
+pub struct BoxPrim {
+    center: Point3,
+    axis: [Vec3; 3],
+    half: [f64; 3],
+    mats: [Arc<MatEnum>; 6],
+    bbox: Aabb,
+}
+
+impl BoxPrim {
+    pub fn from(a: &Point3, b: &Point3, mat: Arc<MatEnum>) -> BoxPrim {
+        // Returns the axis aligned box that contains the two opposite vertices a & b.
+        let center = 0.5 * &(a + b);
+        let edges = [
+            Vec3::from(f64::abs(b.x() - a.x()), 0.0, 0.0),
+            Vec3::from(0.0, f64::abs(b.y() - a.y()), 0.0),
+            Vec3::from(0.0, 0.0, f64::abs(b.z() - a.z())),
+        ];
+        BoxPrim::from_oriented(center, edges, mat)
+    }
+
+    pub fn from_oriented(center: Point3, edges: [Vec3; 3], mat: Arc<MatEnum>) -> BoxPrim {
+        // The three edge vectors are expected to be mutually orthogonal; each spans the full
+        // width of the box along its direction.
+        let mut this = BoxPrim {
+            center,
+            axis: [
+                Vec3::unit_vector(&edges[0]),
+                Vec3::unit_vector(&edges[1]),
+                Vec3::unit_vector(&edges[2]),
+            ],
+            half: [
+                0.5 * edges[0].length(),
+                0.5 * edges[1].length(),
+                0.5 * edges[2].length(),
+            ],
+            mats: [
+                mat.clone(),
+                mat.clone(),
+                mat.clone(),
+                mat.clone(),
+                mat.clone(),
+                mat,
+            ],
+            bbox: Aabb::blank(),
+        };
+        this.set_bounding_box();
+        this
+    }
+
+    pub fn set_face_material(&mut self, face: usize, mat: Arc<MatEnum>) {
+        // Faces are numbered 2 * axis for the negative side and 2 * axis + 1 for the positive
+        // side, so 0..6 is -x, +x, -y, +y, -z, +z in the box's own frame.
+        self.mats[face] = mat;
+    }
+
+    pub fn set_bounding_box(&mut self) {
+        let mut min = Point3::from(INFINITY, INFINITY, INFINITY);
+        let mut max = Point3::from(-INFINITY, -INFINITY, -INFINITY);
+        for i in [-1.0, 1.0] {
+            for j in [-1.0, 1.0] {
+                for k in [-1.0, 1.0] {
+                    let corner = &(&self.center + &(i * self.half[0] * &self.axis[0]))
+                        + &(&(j * self.half[1] * &self.axis[1])
+                            + &(k * self.half[2] * &self.axis[2]));
+                    for c in 0..3 {
+                        min[c] = f64::min(min[c], corner[c]);
+                        max[c] = f64::max(max[c], corner[c]);
+                    }
+                }
+            }
+        }
+        self.bbox = Aabb::from_points(&min, &max).pad();
+    }
+
+    fn face_normal(&self, face: usize) -> Vec3 {
+        if face % 2 == 1 {
+            self.axis[face / 2]
+        } else {
+            -&self.axis[face / 2]
+        }
+    }
+
+    fn face_center(&self, face: usize) -> Point3 {
+        &self.center + &(self.half[face / 2] * &self.face_normal(face))
+    }
+
+    fn face_area(&self, face: usize) -> f64 {
+        let a = face / 2;
+        4.0 * self.half[(a + 1) % 3] * self.half[(a + 2) % 3]
+    }
+
+    fn sampled_faces(&self, o: &Point3) -> Vec<usize> {
+        // Only the faces turned towards the origin can be seen from it. From inside the box
+        // every face is visible. Of those, only the ones that emit are worth sampling, unless
+        // none do and the box sends no light towards the origin at all.
+        let mut visible: Vec<usize> = (0..6)
+            .filter(|&f| Vec3::dot(&self.face_normal(f), &(o - &self.face_center(f))) > 0.0)
+            .collect();
+        if visible.is_empty() {
+            visible = (0..6).collect();
+        }
+        let emitting: Vec<usize> = visible
+            .iter()
+            .copied()
+            .filter(|&f| self.mats[f].is_emissive())
+            .collect();
+        if emitting.is_empty() {
+            visible
+        } else {
+            emitting
+        }
+    }
+
+    fn hit_face(&self, r: &Ray, ray_t: &Interval) -> Option<(f64, usize)> {
+        // Slab test in the box's own frame, remembering which face bounds each end.
+        let oc = r.origin() - &self.center;
+        let mut t_near = -INFINITY;
+        let mut t_far = INFINITY;
+        let mut near_face = 0;
+        let mut far_face = 0;
+        for a in 0..3 {
+            let o = Vec3::dot(&oc, &self.axis[a]);
+            let d = Vec3::dot(r.direction(), &self.axis[a]);
+            if f64::abs(d) < 1e-12 {
+                // Parallel to this slab; miss unless the origin is between its planes.
+                if f64::abs(o) > self.half[a] {
+                    return None;
+                }
+                continue;
+            }
+            let mut t0 = (-self.half[a] - o) / d;
+            let mut t1 = (self.half[a] - o) / d;
+            let mut f0 = 2 * a;
+            let mut f1 = 2 * a + 1;
+            if d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+                std::mem::swap(&mut f0, &mut f1);
+            }
+            if t0 > t_near {
+                t_near = t0;
+                near_face = f0;
+            }
+            if t1 < t_far {
+                t_far = t1;
+                far_face = f1;
+            }
+            if t_far < t_near {
+                return None;
+            }
+        }
+
+        // Find the nearest crossing that lies in the acceptable range.
+        if ray_t.surrounds(t_near) {
+            Some((t_near, near_face))
+        } else if ray_t.surrounds(t_far) {
+            Some((t_far, far_face))
+        } else {
+            None
+        }
+    }
+}
+
+impl Hittable for BoxPrim {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let (t, face) = self.hit_face(r, ray_t)?;
+
+        // Per-face UVs from the two in-plane local coordinates.
+        let p = r.at(t);
+        let local = &p - &self.center;
+        let a = face / 2;
+        let j = (a + 1) % 3;
+        let k = (a + 2) % 3;
+        let u = (Vec3::dot(&local, &self.axis[j]) + self.half[j]) / (2.0 * self.half[j]);
+        let v = (Vec3::dot(&local, &self.axis[k]) + self.half[k]) / (2.0 * self.half[k]);
+
+        let mut rec = HitRecord {
+            p,
+            normal: Vec3::blank(),
+            t,
+            mat: self.mats[face].deref().clone(),
+            front_face: false,
+            u,
+            v,
+            color: None,
+        };
+        rec.set_face_normal(r, &self.face_normal(face));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox.clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        let (t, face) = match self.hit_face(
+            &Ray::from_set_time(*o, *v, time),
+            &Interval::from(0.001, INFINITY),
+        ) {
+            None => {
+                return 0.0;
+            }
+            Some(x) => x,
+        };
+
+        let faces = self.sampled_faces(o);
+        if !faces.contains(&face) {
+            return 0.0;
+        }
+        let area: f64 = faces.iter().map(|&f| self.face_area(f)).sum();
+        let distance_squared = t * t * v.length_squared();
+        let cosine = f64::abs(Vec3::dot(v, &self.face_normal(face)) / v.length());
+
+        distance_squared / (cosine * area)
+    }
+
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        // Pick a sampled face in proportion to its area, then a uniform point on it.
+        let faces = self.sampled_faces(o);
+        let area: f64 = faces.iter().map(|&f| self.face_area(f)).sum();
+        let mut pick = random_double() * area;
+        let mut face = faces[faces.len() - 1];
+        for &f in &faces {
+            pick -= self.face_area(f);
+            if pick <= 0.0 {
+                face = f;
+                break;
+            }
+        }
+
+        let a = face / 2;
+        let j = (a + 1) % 3;
+        let k = (a + 2) % 3;
+        let p = &(&self.face_center(face)
+            + &((2.0 * random_double() - 1.0) * self.half[j] * &self.axis[j]))
+            + &((2.0 * random_double() - 1.0) * self.half[k] * &self.axis[k]);
+        &p - o
+    }
+
+    fn power(&self) -> f64 {
+        (0..6)
+            .map(|f| emitted_power(&self.mats[f], self.face_area(f)))
+            .sum()
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mats.iter().any(|m| m.is_emissive()) {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
+}
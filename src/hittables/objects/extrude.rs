@@ -0,0 +1,352 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{area_pdf_value, beam_cone, emitted_power, HitRecord, Hittable};
+use crate::hittables::objects::profile::Profile;
+use crate::materials::material::Material;
+use crate::materials::MatEnum;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, INFINITY};
+
+// One edge of the profile polygon swept along the extrusion direction.
+#[derive(Clone)]
+struct ExtrudeSide {
+    a0: f64,
+    b0: f64,
+    a1: f64,
+    b1: f64,
+    // Outward world space normals at each end of the edge, and of the side itself.
+    n0: Vec3,
+    n1: Vec3,
+    flat: Vec3,
+    // Perimeter fraction at each end of the edge.
+    u0: f64,
+    u1: f64,
+}
+
+#[derive(Clone)]
+pub struct Extrude {
+    q: Point3,
+    u: Vec3,
+    v: Vec3,
+    direction: Vec3,
+    // Rows of the inverse of the [u v direction] basis, mapping world offsets to (a, b, c).
+    inv: [Vec3; 3],
+    polygon: Vec<Point3>,
+    sides: Vec<ExtrudeSide>,
+    top_normal: Vec3,
+    cap_min: Point3,
+    cap_size: Vec3,
+    // Area of each side, followed by that of one cap.
+    areas: Vec<f64>,
+    area: f64,
+    mat: Arc<MatEnum>,
+    bbox: Aabb,
+}
+
+impl Extrude {
+    pub fn from(
+        profile: &Profile,
+        q: Point3,
+        u: Vec3,
+        v: Vec3,
+        direction: Vec3,
+        mat: Arc<MatEnum>,
+    ) -> Extrude {
+        // The profile is a closed polygon in the plane spanned by u and v at q. It is swept
+        // along `direction` and capped at both ends.
+        let mut polygon = profile.points().clone();
+        if signed_area(&polygon) < 0.0 {
+            polygon.reverse();
+        }
+        let n = polygon.len();
+
+        let det = Vec3::dot(&u, &Vec3::cross(&v, &direction));
+        if f64::abs(det) <= 1e-9 * u.length() * v.length() * direction.length() {
+            panic!("Extrude Direction Should Not Lie In The Profile's Plane!");
+        }
+        let inv = [
+            &Vec3::cross(&v, &direction) / det,
+            &Vec3::cross(&direction, &u) / det,
+            &Vec3::cross(&u, &v) / det,
+        ];
+
+        // Flat outward normal of every edge; with a counter-clockwise polygon the outward
+        // in-plane direction of edge (ea, eb) is (eb, -ea).
+        let mut flat_normals = vec![];
+        let mut lengths = vec![];
+        let mut areas = vec![];
+        for i in 0..n {
+            let e = &polygon[(i + 1) % n] - &polygon[i];
+            let edge = &(e.x() * &u) + &(e.y() * &v);
+            let out = &(e.y() * &u) - &(e.x() * &v);
+            let mut normal = Vec3::unit_vector(&Vec3::cross(&edge, &direction));
+            if Vec3::dot(&normal, &out) < 0.0 {
+                normal = -&normal;
+            }
+            flat_normals.push(normal);
+            lengths.push(edge.length());
+            areas.push(Vec3::cross(&edge, &direction).length());
+        }
+        let perimeter: f64 = lengths.iter().sum();
+
+        let mut sides = vec![];
+        let mut travelled = 0.0;
+        for i in 0..n {
+            let (n0, n1) = if profile.is_smooth() {
+                (
+                    Vec3::unit_vector(&(&flat_normals[(i + n - 1) % n] + &flat_normals[i])),
+                    Vec3::unit_vector(&(&flat_normals[i] + &flat_normals[(i + 1) % n])),
+                )
+            } else {
+                (flat_normals[i], flat_normals[i])
+            };
+            let u0 = travelled / perimeter;
+            travelled += lengths[i];
+            sides.push(ExtrudeSide {
+                a0: polygon[i].x(),
+                b0: polygon[i].y(),
+                a1: polygon[(i + 1) % n].x(),
+                b1: polygon[(i + 1) % n].y(),
+                n0,
+                n1,
+                flat: flat_normals[i],
+                u0,
+                u1: travelled / perimeter,
+            });
+        }
+
+        let mut top_normal = Vec3::unit_vector(&Vec3::cross(&u, &v));
+        if Vec3::dot(&top_normal, &direction) < 0.0 {
+            top_normal = -&top_normal;
+        }
+
+        let mut cap_min = Point3::from(INFINITY, INFINITY, 0.0);
+        let mut cap_max = Point3::from(-INFINITY, -INFINITY, 0.0);
+        let mut min = Point3::from(INFINITY, INFINITY, INFINITY);
+        let mut max = Point3::from(-INFINITY, -INFINITY, -INFINITY);
+        for p in &polygon {
+            for c in 0..2 {
+                cap_min[c] = f64::min(cap_min[c], p[c]);
+                cap_max[c] = f64::max(cap_max[c], p[c]);
+            }
+            let bottom = &q + &(&(p.x() * &u) + &(p.y() * &v));
+            let top = &bottom + &direction;
+            for c in 0..3 {
+                min[c] = f64::min(min[c], f64::min(bottom[c], top[c]));
+                max[c] = f64::max(max[c], f64::max(bottom[c], top[c]));
+            }
+        }
+
+        areas.push(f64::abs(signed_area(&polygon)) * Vec3::cross(&u, &v).length());
+        let area = areas.iter().sum::<f64>() + areas[n];
+
+        Extrude {
+            q,
+            u,
+            v,
+            direction,
+            inv,
+            polygon,
+            sides,
+            top_normal,
+            cap_min,
+            cap_size: &cap_max - &cap_min,
+            areas,
+            area,
+            mat,
+            bbox: Aabb::from_points(&min, &max).pad(),
+        }
+    }
+
+    fn is_inside_polygon(&self, a: f64, b: f64) -> bool {
+        // Even-odd crossing test against the profile polygon.
+        let n = self.polygon.len();
+        let mut inside = false;
+        for i in 0..n {
+            let p0 = &self.polygon[i];
+            let p1 = &self.polygon[(i + 1) % n];
+            if (p0.y() > b) != (p1.y() > b) {
+                let crossing = p0.x() + (b - p0.y()) * (p1.x() - p0.x()) / (p1.y() - p0.y());
+                if a < crossing {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    fn hit_with(&self, r: &Ray, ray_t: &Interval, smooth: bool) -> Option<HitRecord> {
+        // With `smooth` off the sides have their own flat normals, ignoring any smoothing
+        // between them.
+        self.bbox.hit(r, ray_t)?;
+
+        // Express the ray in extrusion coordinates: point = q + a*u + b*v + c*direction.
+        let offset = r.origin() - &self.q;
+        let o = Vec3::from(
+            Vec3::dot(&self.inv[0], &offset),
+            Vec3::dot(&self.inv[1], &offset),
+            Vec3::dot(&self.inv[2], &offset),
+        );
+        let d = Vec3::from(
+            Vec3::dot(&self.inv[0], r.direction()),
+            Vec3::dot(&self.inv[1], r.direction()),
+            Vec3::dot(&self.inv[2], r.direction()),
+        );
+
+        let mut closest_so_far = ray_t.max;
+        let mut hit = None;
+
+        // Caps at c = 0 and c = 1.
+        if f64::abs(d.z()) > 1e-12 {
+            for c in [0.0, 1.0] {
+                let t = (c - o.z()) / d.z();
+                if !Interval::from(ray_t.min, closest_so_far).surrounds(t) {
+                    continue;
+                }
+                let a = o.x() + t * d.x();
+                let b = o.y() + t * d.y();
+                if self.is_inside_polygon(a, b) {
+                    closest_so_far = t;
+                    let normal = if c == 0.0 {
+                        -&self.top_normal
+                    } else {
+                        self.top_normal
+                    };
+                    let u = (a - self.cap_min.x()) / self.cap_size.x();
+                    let v = (b - self.cap_min.y()) / self.cap_size.y();
+                    hit = Some((t, normal, u, v));
+                }
+            }
+        }
+
+        // Sides: intersect the ray's 2D projection with each edge, then check c is in [0, 1].
+        for side in &self.sides {
+            let ea = side.a1 - side.a0;
+            let eb = side.b1 - side.b0;
+            let denom = d.x() * eb - d.y() * ea;
+            if f64::abs(denom) < 1e-12 {
+                continue;
+            }
+            let wa = side.a0 - o.x();
+            let wb = side.b0 - o.y();
+            let t = (wa * eb - wb * ea) / denom;
+            let s = (wa * d.y() - wb * d.x()) / denom;
+            if !(0.0..=1.0).contains(&s) || !Interval::from(ray_t.min, closest_so_far).surrounds(t)
+            {
+                continue;
+            }
+            let c = o.z() + t * d.z();
+            if !(0.0..=1.0).contains(&c) {
+                continue;
+            }
+            closest_so_far = t;
+            let normal = if smooth {
+                Vec3::unit_vector(&(&((1.0 - s) * &side.n0) + &(s * &side.n1)))
+            } else {
+                side.flat
+            };
+            hit = Some((t, normal, side.u0 + s * (side.u1 - side.u0), c));
+        }
+
+        let (t, outward_normal, u, v) = hit?;
+        let mut rec = HitRecord {
+            p: r.at(t),
+            normal: Vec3::blank(),
+            t,
+            mat: self.mat.deref().clone(),
+            front_face: false,
+            u,
+            v,
+            color: None,
+        };
+        rec.set_face_normal(r, &outward_normal);
+
+        Some(rec)
+    }
+}
+
+fn signed_area(polygon: &[Point3]) -> f64 {
+    let n = polygon.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let p0 = &polygon[i];
+        let p1 = &polygon[(i + 1) % n];
+        area += p0.x() * p1.y() - p1.x() * p0.y();
+    }
+    0.5 * area
+}
+
+impl Hittable for Extrude {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        self.hit_with(r, ray_t, true)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox.clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        area_pdf_value(o, v, time, self.area, |r, ray_t| {
+            self.hit_with(r, ray_t, false)
+        })
+    }
+
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        // Pick a side or cap in proportion to its area, then a uniform point on it. Points on
+        // a cap are drawn from the profile's bounds until one lands inside the polygon.
+        let n = self.sides.len();
+        let mut pick = random_double() * self.area;
+        let mut face = n + 1;
+        for f in 0..n + 2 {
+            pick -= self.areas[usize::min(f, n)];
+            if pick <= 0.0 {
+                face = f;
+                break;
+            }
+        }
+        let (a, b, c) = if face < n {
+            let side = &self.sides[face];
+            let s = random_double();
+            (
+                side.a0 + s * (side.a1 - side.a0),
+                side.b0 + s * (side.b1 - side.b0),
+                random_double(),
+            )
+        } else {
+            loop {
+                let a = self.cap_min.x() + random_double() * self.cap_size.x();
+                let b = self.cap_min.y() + random_double() * self.cap_size.y();
+                if self.is_inside_polygon(a, b) {
+                    break (a, b, (face - n) as f64);
+                }
+            }
+        };
+        let p = &(&(&self.q + &(a * &self.u)) + &(b * &self.v)) + &(c * &self.direction);
+        &p - o
+    }
+
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, self.area)
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::all())
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() && self.area > 0.0 {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
+}
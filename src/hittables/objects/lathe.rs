@@ -0,0 +1,364 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{area_pdf_value, beam_cone, emitted_power, HitRecord, Hittable};
+use crate::hittables::objects::profile::Profile;
+use crate::materials::material::Material;
+use crate::materials::MatEnum;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, INFINITY, PI};
+
+// One span of the profile revolved around the axis: a cone frustum, a cylinder, or a flat ring.
+#[derive(Clone)]
+struct LatheSegment {
+    r0: f64,
+    y0: f64,
+    r1: f64,
+    y1: f64,
+    // 2D outward normals (radial, axial) at each end of the span.
+    n0: Vec3,
+    n1: Vec3,
+    // Arc-length fraction along the profile at each end of the span.
+    v0: f64,
+    v1: f64,
+}
+
+impl LatheSegment {
+    fn hit(&self, o: &Vec3, d: &Vec3, ray_t: &Interval) -> Option<f64> {
+        let dy = self.y1 - self.y0;
+        if f64::abs(dy) < 1e-12 {
+            // Flat ring in the plane y = y0.
+            if f64::abs(d.y()) < 1e-12 {
+                return None;
+            }
+            let t = (self.y0 - o.y()) / d.y();
+            if !ray_t.surrounds(t) {
+                return None;
+            }
+            let x = o.x() + t * d.x();
+            let z = o.z() + t * d.z();
+            let radius = f64::sqrt(x * x + z * z);
+            if radius < f64::min(self.r0, self.r1) || radius > f64::max(self.r0, self.r1) {
+                return None;
+            }
+            return Some(t);
+        }
+
+        // Radius varies linearly with height: r(y) = a + b*y.
+        let b = (self.r1 - self.r0) / dy;
+        let a = self.r0 - b * self.y0;
+        let r_at_origin = a + b * o.y();
+
+        let qa = d.x() * d.x() + d.z() * d.z() - b * b * d.y() * d.y();
+        let half_qb = o.x() * d.x() + o.z() * d.z() - r_at_origin * b * d.y();
+        let qc = o.x() * o.x() + o.z() * o.z() - r_at_origin * r_at_origin;
+
+        let mut roots = [INFINITY, INFINITY];
+        if f64::abs(qa) < 1e-12 {
+            if f64::abs(half_qb) < 1e-12 {
+                return None;
+            }
+            roots[0] = -qc / (2.0 * half_qb);
+        } else {
+            let discriminant = half_qb * half_qb - qa * qc;
+            if discriminant < 0.0 {
+                return None;
+            }
+            let sqrtd = discriminant.sqrt();
+            roots[0] = (-half_qb - sqrtd) / qa;
+            roots[1] = (-half_qb + sqrtd) / qa;
+            if roots[1] < roots[0] {
+                roots.swap(0, 1);
+            }
+        }
+
+        let y_range = Interval::from(f64::min(self.y0, self.y1), f64::max(self.y0, self.y1));
+        for t in roots {
+            if !ray_t.surrounds(t) {
+                continue;
+            }
+            // Reject hits outside the span and on the mirrored nappe of the cone.
+            let y = o.y() + t * d.y();
+            if y_range.contains(y) && a + b * y >= 0.0 {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn area(&self) -> f64 {
+        // The side of a cone frustum, which for a flat ring is the area between its circles.
+        let slant = f64::sqrt((self.r1 - self.r0).powi(2) + (self.y1 - self.y0).powi(2));
+        PI * (self.r0 + self.r1) * slant
+    }
+
+    fn sample(&self) -> Vec3 {
+        // A uniform point on the span in the lathe's local frame. The area swept grows with
+        // the radius, so the radius squared is uniform between its ends.
+        let s = if f64::abs(self.r1 - self.r0) < 1e-12 {
+            random_double()
+        } else {
+            let r0 = self.r0 * self.r0;
+            let radius = f64::sqrt(r0 + random_double() * (self.r1 * self.r1 - r0));
+            (radius - self.r0) / (self.r1 - self.r0)
+        };
+        let radius = self.r0 + s * (self.r1 - self.r0);
+        let phi = 2.0 * PI * random_double();
+        Vec3::from(
+            radius * f64::cos(phi),
+            self.y0 + s * (self.y1 - self.y0),
+            radius * f64::sin(phi),
+        )
+    }
+
+    fn param(&self, radius: f64, y: f64) -> f64 {
+        // Fraction along the span for a point known to lie on it.
+        let s = if f64::abs(self.y1 - self.y0) > f64::abs(self.r1 - self.r0) {
+            (y - self.y0) / (self.y1 - self.y0)
+        } else {
+            (radius - self.r0) / (self.r1 - self.r0)
+        };
+        Interval::from(0.0, 1.0).clamp(s)
+    }
+}
+
+#[derive(Clone)]
+pub struct Lathe {
+    base: Point3,
+    uvw: Onb,
+    segments: Vec<LatheSegment>,
+    area: f64,
+    mat: Arc<MatEnum>,
+    bbox: Aabb,
+}
+
+impl Lathe {
+    pub fn from(profile: &Profile, base: Point3, axis: Vec3, mat: Arc<MatEnum>) -> Lathe {
+        // The profile holds (radius, height) pairs ordered so that the solid lies to the left
+        // while walking the curve, e.g. bottom to top along the outside of a vase. Open ends
+        // are closed with flat caps down to the axis.
+        let mut points = vec![];
+        let src = profile.points();
+        let (first, last) = match (src.first(), src.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => panic!("Lathe Profile Should Not Be Empty!"),
+        };
+        if first.x() > 0.0 {
+            points.push(Point3::from(0.0, first.y(), 0.0));
+        }
+        for p in src {
+            points.push(Point3::from(f64::max(p.x(), 0.0), p.y(), 0.0));
+        }
+        if last.x() > 0.0 {
+            points.push(Point3::from(0.0, last.y(), 0.0));
+        }
+        let cap_start = if first.x() > 0.0 { 1 } else { 0 };
+        let cap_end = cap_start + src.len() - 1;
+
+        // Flat normal of each span, pointing to the right of the walking direction.
+        let mut flat_normals = vec![];
+        let mut lengths = vec![];
+        for i in 0..points.len() - 1 {
+            let e = &points[i + 1] - &points[i];
+            flat_normals.push(Vec3::unit_vector(&Vec3::from(e.y(), -e.x(), 0.0)));
+            lengths.push(e.length());
+        }
+        let total_length: f64 = lengths.iter().sum();
+
+        let mut segments = vec![];
+        let mut travelled = 0.0;
+        for i in 0..points.len() - 1 {
+            let mut n0 = flat_normals[i];
+            let mut n1 = flat_normals[i];
+            // Average normals at interior vertices of smooth profiles, never across the caps.
+            if profile.is_smooth() && i >= cap_start && i < cap_end {
+                if i > cap_start {
+                    n0 = Vec3::unit_vector(&(&flat_normals[i - 1] + &flat_normals[i]));
+                }
+                if i + 1 < cap_end {
+                    n1 = Vec3::unit_vector(&(&flat_normals[i] + &flat_normals[i + 1]));
+                }
+            }
+            let v0 = travelled / total_length;
+            travelled += lengths[i];
+            let v1 = travelled / total_length;
+            if lengths[i] < 1e-12 {
+                continue;
+            }
+            segments.push(LatheSegment {
+                r0: points[i].x(),
+                y0: points[i].y(),
+                r1: points[i + 1].x(),
+                y1: points[i + 1].y(),
+                n0,
+                n1,
+                v0,
+                v1,
+            });
+        }
+
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&axis);
+
+        let mut this = Lathe {
+            base,
+            uvw,
+            area: segments.iter().map(|s| s.area()).sum(),
+            segments,
+            mat,
+            bbox: Aabb::blank(),
+        };
+        this.set_bounding_box(&points);
+        this
+    }
+
+    fn set_bounding_box(&mut self, points: &[Point3]) {
+        let mut radius: f64 = 0.0;
+        let mut y_min = INFINITY;
+        let mut y_max = -INFINITY;
+        for p in points {
+            radius = f64::max(radius, p.x());
+            y_min = f64::min(y_min, p.y());
+            y_max = f64::max(y_max, p.y());
+        }
+
+        let mut min = Point3::from(INFINITY, INFINITY, INFINITY);
+        let mut max = Point3::from(-INFINITY, -INFINITY, -INFINITY);
+        for x in [-radius, radius] {
+            for y in [y_min, y_max] {
+                for z in [-radius, radius] {
+                    let corner = self.world_from_local(&Vec3::from(x, y, z));
+                    let corner = &self.base + &corner;
+                    for c in 0..3 {
+                        min[c] = f64::min(min[c], corner[c]);
+                        max[c] = f64::max(max[c], corner[c]);
+                    }
+                }
+            }
+        }
+        self.bbox = Aabb::from_points(&min, &max).pad();
+    }
+
+    fn local_from_world(&self, a: &Vec3) -> Vec3 {
+        // Local frame: the radial plane is x/z and the lathe axis is y.
+        Vec3::from(
+            Vec3::dot(a, self.uvw.u()),
+            Vec3::dot(a, self.uvw.w()),
+            Vec3::dot(a, self.uvw.v()),
+        )
+    }
+
+    fn world_from_local(&self, a: &Vec3) -> Vec3 {
+        self.uvw.local_from_vec3(&Vec3::from(a.x(), a.z(), a.y()))
+    }
+
+    fn hit_with(&self, r: &Ray, ray_t: &Interval, smooth: bool) -> Option<HitRecord> {
+        // With `smooth` off the normal is the span's own, ignoring any smoothing between
+        // spans.
+        self.bbox.hit(r, ray_t)?;
+
+        // Change the ray from world space to the lathe's local frame.
+        let o = self.local_from_world(&(r.origin() - &self.base));
+        let d = self.local_from_world(r.direction());
+
+        let mut closest_so_far = ray_t.max;
+        let mut hit_segment = None;
+        for segment in &self.segments {
+            if let Some(t) = segment.hit(&o, &d, &Interval::from(ray_t.min, closest_so_far)) {
+                closest_so_far = t;
+                hit_segment = Some(segment);
+            }
+        }
+        let segment = hit_segment?;
+
+        let t = closest_so_far;
+        let local_p = &o + &(t * &d);
+        let radius = f64::sqrt(local_p.x() * local_p.x() + local_p.z() * local_p.z());
+        let (cos_phi, sin_phi) = if radius > 1e-12 {
+            (local_p.x() / radius, local_p.z() / radius)
+        } else {
+            (1.0, 0.0)
+        };
+
+        // Interpolate the profile normal along the span and revolve it to the hit angle.
+        let s = segment.param(radius, local_p.y());
+        let n2 = if smooth {
+            &((1.0 - s) * &segment.n0) + &(s * &segment.n1)
+        } else {
+            Vec3::from(segment.y1 - segment.y0, segment.r0 - segment.r1, 0.0)
+        };
+        let local_normal = Vec3::from(n2.x() * cos_phi, n2.y(), n2.x() * sin_phi);
+        let outward_normal = Vec3::unit_vector(&self.world_from_local(&local_normal));
+
+        let phi = f64::atan2(-local_p.z(), local_p.x()) + PI;
+        let mut rec = HitRecord {
+            p: r.at(t),
+            normal: Vec3::blank(),
+            t,
+            mat: self.mat.deref().clone(),
+            front_face: false,
+            u: phi / (2.0 * PI),
+            v: segment.v0 + s * (segment.v1 - segment.v0),
+            color: None,
+        };
+        rec.set_face_normal(r, &outward_normal);
+
+        Some(rec)
+    }
+}
+
+impl Hittable for Lathe {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        self.hit_with(r, ray_t, true)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox.clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        area_pdf_value(o, v, time, self.area, |r, ray_t| {
+            self.hit_with(r, ray_t, false)
+        })
+    }
+
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        // Pick a span in proportion to its area, then a uniform point on it.
+        let mut pick = random_double() * self.area;
+        let mut segment = &self.segments[self.segments.len() - 1];
+        for s in &self.segments {
+            pick -= s.area();
+            if pick <= 0.0 {
+                segment = s;
+                break;
+            }
+        }
+        let p = &self.base + &self.world_from_local(&segment.sample());
+        &p - o
+    }
+
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, self.area)
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::all())
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() && self.area > 0.0 {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
+}
@@ -1,2 +1,8 @@
+pub mod box_prim;
+pub mod extrude;
+pub mod lathe;
+pub mod planar;
+pub mod profile;
 pub mod quad;
 pub mod sphere;
+pub mod sphere_soup;
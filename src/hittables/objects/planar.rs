@@ -0,0 +1,474 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{beam_cone, emitted_power, HitRecord, Hittable};
+use crate::materials::material::Material;
+use crate::materials::MatEnum;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::spherical::{
+    SphericalTriangle, MAX_SPHERICAL_SOLID_ANGLE, MIN_SPHERICAL_SOLID_ANGLE,
+};
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, INFINITY, PI};
+
+// The plane shared by every planar primitive: an origin q, two edge vectors u and v, and the
+// values needed to turn a hit point into plane coordinates (alpha, beta).
+#[derive(Clone)]
+pub struct PlaneFrame {
+    pub(crate) q: Point3,
+    pub(crate) u: Vec3,
+    pub(crate) v: Vec3,
+    pub(crate) normal: Vec3,
+    d: f64,
+    w: Vec3,
+}
+
+impl PlaneFrame {
+    pub fn from(q: Point3, u: Vec3, v: Vec3) -> PlaneFrame {
+        let n = Vec3::cross(&u, &v);
+        let normal = Vec3::unit_vector(&n);
+        PlaneFrame {
+            q,
+            u,
+            v,
+            normal,
+            d: Vec3::dot(&normal, &q),
+            w: &n / Vec3::dot(&n, &n),
+        }
+    }
+
+    pub fn parallelogram_area(&self) -> f64 {
+        Vec3::cross(&self.u, &self.v).length()
+    }
+
+    pub fn point(&self, alpha: f64, beta: f64) -> Point3 {
+        &self.q + &(&(alpha * &self.u) + &(beta * &self.v))
+    }
+
+    pub fn intersect(&self, r: &Ray, ray_t: &Interval) -> Option<(f64, Point3, f64, f64)> {
+        // Returns the ray parameter, the hit point and its plane coordinates.
+        let denom = Vec3::dot(&self.normal, r.direction());
+
+        // No hit if the ray is parallel to the plane.
+        if f64::abs(denom) < 1e-8 {
+            return None;
+        }
+
+        // Return false if the hit point parameter t is outside the ray interval.
+        let t = (self.d - Vec3::dot(&self.normal, r.origin())) / denom;
+        if !ray_t.contains(t) {
+            return None;
+        }
+
+        let intersection = r.at(t);
+        let planar_hitpt_vector = &intersection - &self.q;
+        let alpha = Vec3::dot(&self.w, &Vec3::cross(&planar_hitpt_vector, &self.v));
+        let beta = Vec3::dot(&self.w, &Vec3::cross(&self.u, &planar_hitpt_vector));
+        Some((t, intersection, alpha, beta))
+    }
+}
+
+#[derive(Clone)]
+pub enum PlanarShape {
+    // Vertices q, q + u and q + v.
+    Triangle,
+    // Centered on q with semi-axes u and v.
+    Ellipse,
+    // Centered on q with outer semi-axes u and v; `inner` is the hole's fraction of them.
+    Annulus {
+        inner: f64,
+    },
+    // Simple polygon with vertices given in plane coordinates, triangulated for sampling.
+    Polygon {
+        points: Vec<Point3>,
+        triangles: Vec<[usize; 3]>,
+        cdf: Vec<f64>,
+        min: Point3,
+        size: Vec3,
+    },
+}
+
+impl PlanarShape {
+    fn interior_uv(&self, a: f64, b: f64) -> Option<(f64, f64)> {
+        // Given the hit point in plane coordinates, return None if it is outside the
+        // primitive, otherwise the hit record UV coordinates.
+        match self {
+            PlanarShape::Triangle => {
+                if a < 0.0 || b < 0.0 || a + b > 1.0 {
+                    return None;
+                }
+                Some((a, b))
+            }
+            PlanarShape::Ellipse => {
+                if a * a + b * b > 1.0 {
+                    return None;
+                }
+                Some((0.5 * (a + 1.0), 0.5 * (b + 1.0)))
+            }
+            PlanarShape::Annulus { inner } => {
+                let radius = f64::sqrt(a * a + b * b);
+                if radius > 1.0 || radius < *inner {
+                    return None;
+                }
+                let phi = f64::atan2(b, a) + PI;
+                Some((phi / (2.0 * PI), (radius - inner) / (1.0 - inner)))
+            }
+            PlanarShape::Polygon {
+                points, min, size, ..
+            } => {
+                if !is_inside_polygon(points, a, b) {
+                    return None;
+                }
+                Some(((a - min.x()) / size.x(), (b - min.y()) / size.y()))
+            }
+        }
+    }
+
+    fn area_fraction(&self) -> f64 {
+        // Area in plane coordinates, i.e. relative to the parallelogram spanned by u and v.
+        match self {
+            PlanarShape::Triangle => 0.5,
+            PlanarShape::Ellipse => PI,
+            PlanarShape::Annulus { inner } => PI * (1.0 - inner * inner),
+            PlanarShape::Polygon { points, .. } => f64::abs(signed_area(points)),
+        }
+    }
+
+    fn sample(&self) -> (f64, f64) {
+        // Uniformly distributed plane coordinates inside the shape.
+        match self {
+            PlanarShape::Triangle => sample_triangle(),
+            PlanarShape::Ellipse => {
+                let radius = f64::sqrt(random_double());
+                let phi = 2.0 * PI * random_double();
+                (radius * f64::cos(phi), radius * f64::sin(phi))
+            }
+            PlanarShape::Annulus { inner } => {
+                let radius = f64::sqrt(inner * inner + random_double() * (1.0 - inner * inner));
+                let phi = 2.0 * PI * random_double();
+                (radius * f64::cos(phi), radius * f64::sin(phi))
+            }
+            PlanarShape::Polygon {
+                points,
+                triangles,
+                cdf,
+                ..
+            } => {
+                let pick = random_double() * cdf[cdf.len() - 1];
+                let index = usize::min(cdf.partition_point(|&c| c < pick), triangles.len() - 1);
+                let [i, j, k] = triangles[index];
+                let (s, t) = sample_triangle();
+                let p = &(&points[i] + &(s * &(&points[j] - &points[i])))
+                    + &(t * &(&points[k] - &points[i]));
+                (p.x(), p.y())
+            }
+        }
+    }
+}
+
+fn sample_triangle() -> (f64, f64) {
+    let s = random_double();
+    let t = random_double();
+    if s + t > 1.0 {
+        (1.0 - s, 1.0 - t)
+    } else {
+        (s, t)
+    }
+}
+
+fn signed_area(points: &[Point3]) -> f64 {
+    let n = points.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let p0 = &points[i];
+        let p1 = &points[(i + 1) % n];
+        area += p0.x() * p1.y() - p1.x() * p0.y();
+    }
+    0.5 * area
+}
+
+fn is_inside_polygon(points: &[Point3], a: f64, b: f64) -> bool {
+    // Even-odd crossing test.
+    let n = points.len();
+    let mut inside = false;
+    for i in 0..n {
+        let p0 = &points[i];
+        let p1 = &points[(i + 1) % n];
+        if (p0.y() > b) != (p1.y() > b) {
+            let crossing = p0.x() + (b - p0.y()) * (p1.x() - p0.x()) / (p1.y() - p0.y());
+            if a < crossing {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn cross_2d(o: &Point3, a: &Point3, b: &Point3) -> f64 {
+    (a.x() - o.x()) * (b.y() - o.y()) - (a.y() - o.y()) * (b.x() - o.x())
+}
+
+fn triangulate(points: &[Point3]) -> Vec<[usize; 3]> {
+    // Ear clipping; works for any simple polygon.
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0.0 {
+        remaining.reverse();
+    }
+    let mut triangles = vec![];
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let mut clipped = false;
+        for i in 0..n {
+            let prev = remaining[(i + n - 1) % n];
+            let curr = remaining[i];
+            let next = remaining[(i + 1) % n];
+            if cross_2d(&points[prev], &points[curr], &points[next]) <= 0.0 {
+                continue;
+            }
+            let is_ear = remaining.iter().all(|&other| {
+                other == prev
+                    || other == curr
+                    || other == next
+                    || cross_2d(&points[prev], &points[curr], &points[other]) < 0.0
+                    || cross_2d(&points[curr], &points[next], &points[other]) < 0.0
+                    || cross_2d(&points[next], &points[prev], &points[other]) < 0.0
+            });
+            if is_ear {
+                triangles.push([prev, curr, next]);
+                remaining.remove(i);
+                clipped = true;
+                break;
+            }
+        }
+        if !clipped {
+            // Degenerate input; stop rather than loop forever.
+            break;
+        }
+    }
+    if remaining.len() == 3 {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    triangles
+}
+
+#[derive(Clone)]
+pub struct Planar {
+    plane: PlaneFrame,
+    shape: PlanarShape,
+    mat: Arc<MatEnum>,
+    bbox: Aabb,
+    area: f64,
+}
+
+impl Planar {
+    pub fn from(plane: PlaneFrame, shape: PlanarShape, mat: Arc<MatEnum>) -> Planar {
+        let area = shape.area_fraction() * plane.parallelogram_area();
+        let mut this = Planar {
+            plane,
+            shape,
+            mat,
+            bbox: Aabb::blank(),
+            area,
+        };
+        this.set_bounding_box();
+        this
+    }
+
+    pub fn triangle(q: Point3, u: Vec3, v: Vec3, mat: Arc<MatEnum>) -> Planar {
+        Planar::from(PlaneFrame::from(q, u, v), PlanarShape::Triangle, mat)
+    }
+
+    pub fn ellipse(center: Point3, u: Vec3, v: Vec3, mat: Arc<MatEnum>) -> Planar {
+        Planar::from(PlaneFrame::from(center, u, v), PlanarShape::Ellipse, mat)
+    }
+
+    pub fn disk(center: Point3, normal: Vec3, radius: f64, mat: Arc<MatEnum>) -> Planar {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&normal);
+        Planar::ellipse(center, radius * uvw.u(), radius * uvw.v(), mat)
+    }
+
+    pub fn annulus(
+        center: Point3,
+        normal: Vec3,
+        inner_radius: f64,
+        outer_radius: f64,
+        mat: Arc<MatEnum>,
+    ) -> Planar {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&normal);
+        Planar::from(
+            PlaneFrame::from(center, outer_radius * uvw.u(), outer_radius * uvw.v()),
+            PlanarShape::Annulus {
+                inner: inner_radius / outer_radius,
+            },
+            mat,
+        )
+    }
+
+    pub fn polygon(vertices: &[Point3], mat: Arc<MatEnum>) -> Planar {
+        // The vertices are assumed to be coplanar and to form a simple polygon.
+        let mut normal = Vec3::blank();
+        let n = vertices.len();
+        for i in 0..n {
+            normal += &Vec3::cross(&vertices[i], &vertices[(i + 1) % n]);
+        }
+        let u = Vec3::unit_vector(&(&vertices[1] - &vertices[0]));
+        let v = Vec3::cross(&Vec3::unit_vector(&normal), &u);
+        let plane = PlaneFrame::from(vertices[0], u, v);
+
+        let points: Vec<Point3> = vertices
+            .iter()
+            .map(|p| {
+                let offset = p - &vertices[0];
+                Point3::from(Vec3::dot(&offset, &u), Vec3::dot(&offset, &v), 0.0)
+            })
+            .collect();
+        let triangles = triangulate(&points);
+        let mut cdf = vec![];
+        let mut total = 0.0;
+        for [i, j, k] in &triangles {
+            total += 0.5 * f64::abs(cross_2d(&points[*i], &points[*j], &points[*k]));
+            cdf.push(total);
+        }
+        let mut min = Point3::from(INFINITY, INFINITY, 0.0);
+        let mut max = Point3::from(-INFINITY, -INFINITY, 0.0);
+        for p in &points {
+            for c in 0..2 {
+                min[c] = f64::min(min[c], p[c]);
+                max[c] = f64::max(max[c], p[c]);
+            }
+        }
+
+        Planar::from(
+            plane,
+            PlanarShape::Polygon {
+                points,
+                triangles,
+                cdf,
+                min,
+                size: &max - &min,
+            },
+            mat,
+        )
+    }
+
+    fn spherical_triangle(&self, o: &Point3) -> Option<SphericalTriangle> {
+        // Triangles are sampled uniformly in solid angle unless they look too small or too
+        // large from o for that to be accurate; other shapes always use area sampling.
+        match self.shape {
+            PlanarShape::Triangle => {
+                let p = &self.plane;
+                let tri = SphericalTriangle::from(o, &p.q, &p.point(1.0, 0.0), &p.point(0.0, 1.0))?;
+                if (MIN_SPHERICAL_SOLID_ANGLE..MAX_SPHERICAL_SOLID_ANGLE).contains(&tri.solid_angle)
+                {
+                    Some(tri)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn set_bounding_box(&mut self) {
+        // Bound the shape's extreme points in plane coordinates.
+        let corners = match &self.shape {
+            PlanarShape::Triangle => vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+            PlanarShape::Ellipse | PlanarShape::Annulus { .. } => {
+                vec![(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)]
+            }
+            PlanarShape::Polygon { points, .. } => points.iter().map(|p| (p.x(), p.y())).collect(),
+        };
+        let mut min = Point3::from(INFINITY, INFINITY, INFINITY);
+        let mut max = Point3::from(-INFINITY, -INFINITY, -INFINITY);
+        for (a, b) in corners {
+            let p = self.plane.point(a, b);
+            for c in 0..3 {
+                min[c] = f64::min(min[c], p[c]);
+                max[c] = f64::max(max[c], p[c]);
+            }
+        }
+        self.bbox = Aabb::from_points(&min, &max).pad();
+    }
+}
+
+impl Hittable for Planar {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let (t, intersection, alpha, beta) = self.plane.intersect(r, ray_t)?;
+
+        // Determine the hit point lies within the planar shape using its plane coordinates.
+        let (u, v) = self.shape.interior_uv(alpha, beta)?;
+
+        let mut rec = HitRecord {
+            p: intersection,
+            normal: Vec3::blank(),
+            t,
+            mat: self.mat.deref().clone(),
+            front_face: false,
+            u,
+            v,
+            color: None,
+        };
+        rec.set_face_normal(r, &self.plane.normal);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox.clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        let rec = match self.hit(
+            &Ray::from_set_time(*o, *v, time),
+            &Interval::from(0.001, INFINITY),
+        ) {
+            None => {
+                return 0.0;
+            }
+            Some(x) => x,
+        };
+
+        if let Some(tri) = self.spherical_triangle(o) {
+            return 1.0 / tri.solid_angle;
+        }
+
+        let distance_squared = rec.t * rec.t * v.length_squared();
+        let cosine = f64::abs(Vec3::dot(v, &rec.normal) / v.length());
+
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        if let Some(tri) = self.spherical_triangle(o) {
+            return tri.sample(random_double(), random_double());
+        }
+        let (alpha, beta) = self.shape.sample();
+        &self.plane.point(alpha, beta) - o
+    }
+
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, self.area)
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::one_sided(&self.plane.normal))
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
+}
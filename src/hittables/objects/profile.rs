@@ -0,0 +1,81 @@
+use crate::math_structures::vec3::{Point3, Vec3};
+
+// A 2D profile curve used by the Lathe and Extrude primitives. Points are stored as Vec3s
+// whose x and y components hold the 2D coordinates; z is ignored.
+#[derive(Clone)]
+pub struct Profile {
+    points: Vec<Point3>,
+    smooth: bool,
+}
+
+impl Profile {
+    pub fn from_polyline(points: Vec<Point3>) -> Profile {
+        Profile {
+            points,
+            smooth: false,
+        }
+    }
+
+    pub fn from_spline(control_points: &[Point3], steps_per_span: usize) -> Profile {
+        // Open Catmull-Rom spline through every control point; the end points are repeated
+        // so the curve starts and ends exactly on the first and last control points.
+        let n = control_points.len();
+        let steps = usize::max(steps_per_span, 1);
+        let mut points = vec![];
+        for i in 0..n.saturating_sub(1) {
+            let p0 = &control_points[i.saturating_sub(1)];
+            let p1 = &control_points[i];
+            let p2 = &control_points[i + 1];
+            let p3 = &control_points[usize::min(i + 2, n - 1)];
+            for k in 0..steps {
+                points.push(catmull_rom(p0, p1, p2, p3, k as f64 / steps as f64));
+            }
+        }
+        if let Some(last) = control_points.last() {
+            points.push(*last);
+        }
+        Profile {
+            points,
+            smooth: true,
+        }
+    }
+
+    pub fn from_closed_spline(control_points: &[Point3], steps_per_span: usize) -> Profile {
+        // Closed Catmull-Rom spline; the last control point connects back to the first.
+        let n = control_points.len();
+        let steps = usize::max(steps_per_span, 1);
+        let mut points = vec![];
+        for i in 0..n {
+            let p0 = &control_points[(i + n - 1) % n];
+            let p1 = &control_points[i];
+            let p2 = &control_points[(i + 1) % n];
+            let p3 = &control_points[(i + 2) % n];
+            for k in 0..steps {
+                points.push(catmull_rom(p0, p1, p2, p3, k as f64 / steps as f64));
+            }
+        }
+        Profile {
+            points,
+            smooth: true,
+        }
+    }
+
+    pub fn points(&self) -> &Vec<Point3> {
+        &self.points
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        self.smooth
+    }
+}
+
+fn catmull_rom(p0: &Point3, p1: &Point3, p2: &Point3, p3: &Point3, s: f64) -> Point3 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+    let a = 2.0 * p1;
+    let b = p2 - p0;
+    let c = &(&(2.0 * p0) - &(5.0 * p1)) + &(&(4.0 * p2) - p3);
+    let d = &(&(3.0 * p1) - p0) + &(p3 - &(3.0 * p2));
+    let sum = &(&a + &(s * &b)) + &(&(s2 * &c) + &(s3 * &d));
+    Vec3::from(0.5 * sum.x(), 0.5 * sum.y(), 0.0)
+}
@@ -1,54 +1,67 @@
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable::{beam_cone, emitted_power, HitRecord, Hittable};
 use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::planar::PlaneFrame;
+use crate::materials::diffuse_light::DiffuseLight;
 use crate::materials::lambertian::Lambertian;
+use crate::materials::material::Material;
 use crate::materials::MatEnum;
 use crate::math_structures::aabb::Aabb;
 use crate::math_structures::color::Color;
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
 use crate::math_structures::ray::Ray;
+use crate::math_structures::spherical::{
+    SphericalRectangle, SphericalTriangle, MAX_SPHERICAL_SOLID_ANGLE, MIN_SPHERICAL_SOLID_ANGLE,
+};
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::rtweekend::{random_double, INFINITY};
 
 #[derive(Clone)]
 pub struct Quad {
-    q: Point3,
-    u: Vec3,
-    v: Vec3,
+    plane: PlaneFrame,
     mat: Arc<MatEnum>,
     bbox: Aabb,
-    normal: Vec3,
-    d: f64,
-    w: Vec3,
     area: f64,
+    rectangular: bool,
+}
+
+// How light samples are drawn on a quad from a given origin.
+enum QuadSampler {
+    Rectangle(SphericalRectangle),
+    Triangles(SphericalTriangle, SphericalTriangle),
+    Area,
 }
 
 impl Quad {
     pub fn from(q: Point3, u: Vec3, v: Vec3, mat: Arc<MatEnum>) -> Quad {
+        let plane = PlaneFrame::from(q, u, v);
+        let area = plane.parallelogram_area();
+        let rectangular =
+            f64::abs(Vec3::dot(&plane.u, &plane.v)) < 1e-9 * plane.u.length() * plane.v.length();
         let mut this = Quad {
-            q,
-            u,
-            v,
+            plane,
             mat,
             bbox: Aabb::blank(),
-            normal: Vec3::blank(),
-            d: 0.0,
-            w: Vec3::blank(),
-            area: 0.0,
+            area,
+            rectangular,
         };
-        let n = Vec3::cross(&u, &v);
-        this.normal = Vec3::unit_vector(&n);
-        this.d = Vec3::dot(&this.normal, &this.q);
-        this.w = &n / Vec3::dot(&n, &n);
-
-        this.area = n.length();
-
         this.set_bounding_box();
         this
     }
 
+    pub fn light(q: Point3, u: Vec3, v: Vec3, kelvin: f64, power: f64) -> Quad {
+        // A quad light of the given color temperature and total power, emitting from the side
+        // its normal u x v points to.
+        let area = Vec3::cross(&u, &v).length();
+        let mat = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_kelvin_power(
+            kelvin, power, area,
+        )));
+        Quad::from(q, u, v, mat)
+    }
+
     pub fn make_box(a: &Point3, b: &Point3, mat: Arc<MatEnum>) -> Arc<HittableList> {
         // Returns the 3D box (six sides) that contains the two opposite vertices a & b.
         let mut sides = HittableList::blank();
@@ -110,7 +123,36 @@ impl Quad {
     }
 
     pub fn set_bounding_box(&mut self) {
-        self.bbox = Aabb::from_points(&self.q, &(&(&self.q + &self.u) + &self.v)).pad();
+        self.bbox = Aabb::from_points(&self.plane.q, &self.plane.point(1.0, 1.0)).pad();
+    }
+
+    fn sampler(&self, o: &Point3) -> QuadSampler {
+        // Rectangles are sampled uniformly in solid angle directly, other parallelograms as
+        // two spherical triangles. Area sampling is kept for lights that are tiny or that
+        // surround the origin, where the spherical samplers lose precision.
+        let in_range = |solid_angle: f64| {
+            (MIN_SPHERICAL_SOLID_ANGLE..MAX_SPHERICAL_SOLID_ANGLE).contains(&solid_angle)
+        };
+        let p = &self.plane;
+        if self.rectangular {
+            let rect = SphericalRectangle::from(o, &p.q, &p.u, &p.v);
+            if in_range(rect.solid_angle) {
+                return QuadSampler::Rectangle(rect);
+            }
+            return QuadSampler::Area;
+        }
+        let far = p.point(1.0, 1.0);
+        let q_u = &p.q + &p.u;
+        let q_v = &p.q + &p.v;
+        match (
+            SphericalTriangle::from(o, &p.q, &q_u, &q_v),
+            SphericalTriangle::from(o, &far, &q_v, &q_u),
+        ) {
+            (Some(t0), Some(t1)) if in_range(t0.solid_angle + t1.solid_angle) => {
+                QuadSampler::Triangles(t0, t1)
+            }
+            _ => QuadSampler::Area,
+        }
     }
 
     pub fn is_interior(a: f64, b: f64) -> Option<HitRecord> {
@@ -127,6 +169,7 @@ impl Quad {
             front_face: false,
             u: a,
             v: b,
+            color: None,
         };
         rec.u = a;
         rec.v = b;
@@ -136,25 +179,9 @@ impl Quad {
 
 impl Hittable for Quad {
     fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
-        let denom = Vec3::dot(&self.normal, &r.direction());
-
-        // No hit if the ray is parallel to the plane.
-        if f64::abs(denom) < 1e-8 {
-            return None;
-        }
-
-        // Return false if the hit point parameter t is outside the ray interval.
-        let t = (self.d - Vec3::dot(&self.normal, r.origin())) / denom;
-        if !ray_t.contains(t) {
-            return None;
-        }
+        let (t, intersection, alpha, beta) = self.plane.intersect(r, ray_t)?;
 
         // Determine the hit point lies within the planar shape using its plane coordinates.
-        let intersection = r.at(t);
-        let planar_hitpt_vector = &intersection - &self.q;
-        let alpha = Vec3::dot(&self.w, &Vec3::cross(&planar_hitpt_vector, &self.v));
-        let beta = Vec3::dot(&self.w, &Vec3::cross(&self.u, &planar_hitpt_vector));
-
         let inter_rec;
         match { Quad::is_interior(alpha, beta) } {
             Some(x) => {
@@ -174,8 +201,9 @@ impl Hittable for Quad {
             front_face: false,
             u: inter_rec.u,
             v: inter_rec.v,
+            color: None,
         };
-        rec.set_face_normal(r, &self.normal);
+        rec.set_face_normal(r, &self.plane.normal);
 
         return Some(rec);
     }
@@ -188,25 +216,57 @@ impl Hittable for Quad {
         Box::new((*self).clone())
     }
 
-    fn pdf_value(&self, o: &Point3, v: &Vec3) -> f64 {
-        let rec;
-        match self.hit(&Ray::from(*o, *v), &Interval::from(0.001, INFINITY)) {
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        let rec = match self.hit(
+            &Ray::from_set_time(*o, *v, time),
+            &Interval::from(0.001, INFINITY),
+        ) {
             None => {
                 return 0.0;
             }
-            Some(x) => {
-                rec = x;
+            Some(x) => x,
+        };
+
+        match self.sampler(o) {
+            QuadSampler::Rectangle(rect) => 1.0 / rect.solid_angle,
+            QuadSampler::Triangles(t0, t1) => 1.0 / (t0.solid_angle + t1.solid_angle),
+            QuadSampler::Area => {
+                let distance_squared = rec.t * rec.t * v.length_squared();
+                let cosine = f64::abs(Vec3::dot(v, &rec.normal) / v.length());
+                distance_squared / (cosine * self.area)
             }
         }
+    }
 
-        let distance_squared = rec.t * rec.t * v.length_squared();
-        let cosine = f64::abs(Vec3::dot(&v, &rec.normal) / v.length());
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        match self.sampler(o) {
+            QuadSampler::Rectangle(rect) => &rect.sample(random_double(), random_double()) - o,
+            QuadSampler::Triangles(t0, t1) => {
+                // Pick a triangle in proportion to the solid angle it covers.
+                let total = t0.solid_angle + t1.solid_angle;
+                if random_double() * total < t0.solid_angle {
+                    t0.sample(random_double(), random_double())
+                } else {
+                    t1.sample(random_double(), random_double())
+                }
+            }
+            QuadSampler::Area => &self.plane.point(random_double(), random_double()) - o,
+        }
+    }
 
-        return distance_squared / (cosine * self.area);
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, self.area)
     }
 
-    fn random(&self, o: &Vec3) -> Vec3 {
-        let p = &self.q + &(&(random_double() * &self.u) + &(random_double() * &self.v));
-        return &p - o;
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::one_sided(&self.plane.normal))
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
     }
 }
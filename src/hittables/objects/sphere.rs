@@ -1,10 +1,13 @@
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable::{beam_cone, emitted_power, HitRecord, Hittable};
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::material::Material;
 use crate::materials::MatEnum;
 use crate::math_structures::aabb::Aabb;
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
 use crate::math_structures::onb::Onb;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{Point3, Vec3};
@@ -34,6 +37,15 @@ impl Sphere {
         }
     }
 
+    pub fn light(center: Point3, radius: f64, kelvin: f64, power: f64) -> Sphere {
+        // A spherical light of the given color temperature and total power.
+        let area = 4.0 * PI * radius * radius;
+        let mat = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_kelvin_power(
+            kelvin, power, area,
+        )));
+        Sphere::from(center, radius, mat)
+    }
+
     pub fn from_moving(center1: Point3, center2: Point3, radius: f64, mat: Arc<MatEnum>) -> Sphere {
         let rvec = Vec3::from(radius, radius, radius);
         let box1 = Aabb::from_points(&(&center1 - &rvec), &(&center1 + &rvec));
@@ -56,7 +68,7 @@ impl Sphere {
         return &self.center1 + &(time * &self.center_vec);
     }
 
-    pub fn get_sphere_uv(&self, p: &Point3) -> (f64, f64) {
+    pub fn get_sphere_uv(p: &Point3) -> (f64, f64) {
         // p: a given point on the sphere of radius one, centered at the origin.
         // u: returned value [0,1] of angle around the Y axis from X=-1.
         // v: returned value [0,1] of angle from Y=-1 to Y=+1.
@@ -115,8 +127,8 @@ impl Hittable for Sphere {
 
         let t = root;
         let p = r.at(t);
-        let outward_normal = &(&p - &self.center1) / self.radius;
-        let (u, v) = self.get_sphere_uv(&outward_normal);
+        let outward_normal = &(&p - &center) / self.radius;
+        let (u, v) = Sphere::get_sphere_uv(&outward_normal);
         let mut rec = HitRecord {
             t,
             p,
@@ -125,6 +137,7 @@ impl Hittable for Sphere {
             u,
             mat: self.mat.deref().clone(),
             v,
+            color: None,
         };
         rec.set_face_normal(&r, &outward_normal);
 
@@ -139,29 +152,57 @@ impl Hittable for Sphere {
         Box::from((*self).clone())
     }
 
-    fn pdf_value(&self, o: &Point3, v: &Vec3) -> f64 {
-        // This method only works for stationary spheres.
-        match self.hit(
-            &Ray::from(o.clone(), v.clone()),
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        let rec = match self.hit(
+            &Ray::from_set_time(*o, *v, time),
             &Interval::from(0.001, INFINITY),
         ) {
             None => {
                 return 0.0;
             }
-            Some(_) => {}
+            Some(x) => x,
+        };
+
+        let distance_squared = (&self.center(time) - o).length_squared();
+        if distance_squared <= self.radius * self.radius * (1.0 + 1e-6) {
+            // From inside or on the sphere it covers more than a cone, so sample its surface
+            // uniformly by area instead.
+            let area = 4.0 * PI * self.radius * self.radius;
+            let cosine = f64::abs(Vec3::dot(v, &rec.normal) / v.length());
+            return rec.t * rec.t * v.length_squared() / (cosine * area);
         }
-        let cos_theta_max =
-            f64::sqrt(1.0 - self.radius * self.radius / (&self.center1 - o).length_squared());
+
+        let cos_theta_max = f64::sqrt(1.0 - self.radius * self.radius / distance_squared);
         let solid_angle = 2.0 * PI * (1.0 - cos_theta_max);
 
         1.0 / solid_angle
     }
 
-    fn random(&self, o: &Vec3) -> Vec3 {
-        let direction = &self.center1 - o;
+    fn random(&self, o: &Vec3, time: f64) -> Vec3 {
+        let center = self.center(time);
+        let direction = &center - o;
         let distance_squared = direction.length_squared();
+        if distance_squared <= self.radius * self.radius * (1.0 + 1e-6) {
+            return &(&center + &(self.radius * &Vec3::random_unit_vector())) - o;
+        }
         let mut uvw = Onb::blank();
         uvw.build_from_w(&direction);
         uvw.local_from_vec3(&random_to_sphere(self.radius, distance_squared))
     }
+
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, 4.0 * PI * self.radius * self.radius)
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::all())
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
 }
@@ -0,0 +1,299 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{area_pdf_value, beam_cone, emitted_power, HitRecord, Hittable};
+use crate::hittables::objects::sphere::Sphere;
+use crate::materials::material::Material;
+use crate::materials::MatEnum;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::Color;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, PI};
+
+const MAX_LEAF_SIZE: usize = 4;
+
+// Flattened BVH node. Leaves reference `count` particles starting at `first` in the index
+// list; interior nodes have `count == 0` and their children at `first` and `first + 1`.
+struct SoupNode {
+    bbox: Aabb,
+    first: usize,
+    count: usize,
+}
+
+// The particles and their BVH, shared by every copy of the soup so that handing it out as a
+// light doesn't duplicate them.
+struct Particles {
+    centers: Vec<Point3>,
+    radii: Vec<f64>,
+    colors: Option<Vec<Color>>,
+    indices: Vec<u32>,
+    nodes: Vec<SoupNode>,
+    // Running total of the particles' surface areas, for picking one to sample as a light.
+    cumulative_areas: Vec<f64>,
+}
+
+#[derive(Clone)]
+pub struct SphereSoup {
+    particles: Arc<Particles>,
+    mat: Arc<MatEnum>,
+}
+
+impl SphereSoup {
+    pub fn from(
+        centers: Vec<Point3>,
+        radii: Vec<f64>,
+        colors: Option<Vec<Color>>,
+        mat: Arc<MatEnum>,
+    ) -> SphereSoup {
+        // Many small spheres sharing one material. `radii` and `colors` are indexed like
+        // `centers`.
+        assert_eq!(
+            radii.len(),
+            centers.len(),
+            "Sphere Soup Should Have One Radius Per Center!"
+        );
+        if let Some(colors) = &colors {
+            assert_eq!(
+                colors.len(),
+                centers.len(),
+                "Sphere Soup Should Have One Color Per Center!"
+            );
+        }
+        let cumulative_areas = radii
+            .iter()
+            .scan(0.0, |total, r| {
+                *total += 4.0 * PI * r * r;
+                Some(*total)
+            })
+            .collect();
+        let mut particles = Particles {
+            indices: (0..centers.len() as u32).collect(),
+            cumulative_areas,
+            centers,
+            radii,
+            colors,
+            nodes: vec![],
+        };
+        particles.nodes.push(SoupNode {
+            bbox: Aabb::blank(),
+            first: 0,
+            count: 0,
+        });
+        let n = particles.indices.len();
+        if n > 0 {
+            particles.build(0, 0, n);
+        }
+        SphereSoup {
+            particles: Arc::new(particles),
+            mat,
+        }
+    }
+
+    pub fn from_uniform(centers: Vec<Point3>, radius: f64, mat: Arc<MatEnum>) -> SphereSoup {
+        let radii = vec![radius; centers.len()];
+        SphereSoup::from(centers, radii, None, mat)
+    }
+
+    fn area(&self) -> f64 {
+        self.particles
+            .cumulative_areas
+            .last()
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+impl Particles {
+    fn particle_box(&self, i: usize) -> Aabb {
+        let r = self.radii[i];
+        let rvec = Vec3::from(r, r, r);
+        Aabb::from_points(&(&self.centers[i] - &rvec), &(&self.centers[i] + &rvec))
+    }
+
+    fn build(&mut self, node: usize, start: usize, end: usize) {
+        let mut bbox = self.particle_box(self.indices[start] as usize);
+        let mut centroid_min = self.centers[self.indices[start] as usize];
+        let mut centroid_max = centroid_min;
+        for k in start..end {
+            let i = self.indices[k] as usize;
+            bbox = Aabb::from_aabbs(&bbox, &self.particle_box(i));
+            for c in 0..3 {
+                centroid_min[c] = f64::min(centroid_min[c], self.centers[i][c]);
+                centroid_max[c] = f64::max(centroid_max[c], self.centers[i][c]);
+            }
+        }
+        self.nodes[node].bbox = bbox;
+
+        if end - start <= MAX_LEAF_SIZE {
+            self.nodes[node].first = start;
+            self.nodes[node].count = end - start;
+            return;
+        }
+
+        // Split at the median along the axis with the widest spread of centers.
+        let extent = &centroid_max - &centroid_min;
+        let axis = if extent.x() > extent.y() && extent.x() > extent.z() {
+            0
+        } else if extent.y() > extent.z() {
+            1
+        } else {
+            2
+        };
+        let mid = start + (end - start) / 2;
+        let centers = &self.centers;
+        self.indices[start..end].select_nth_unstable_by(mid - start, |a, b| {
+            f64::total_cmp(&centers[*a as usize][axis], &centers[*b as usize][axis])
+        });
+
+        let left = self.nodes.len();
+        for _ in 0..2 {
+            self.nodes.push(SoupNode {
+                bbox: Aabb::blank(),
+                first: 0,
+                count: 0,
+            });
+        }
+        self.nodes[node].first = left;
+        self.build(left, start, mid);
+        self.build(left + 1, mid, end);
+    }
+
+    fn hit_particle(&self, i: usize, r: &Ray, ray_t: &Interval) -> Option<f64> {
+        let oc = r.origin() - &self.centers[i];
+        let a = r.direction().length_squared();
+        let half_b = Vec3::dot(&oc, r.direction());
+        let c = oc.length_squared() - self.radii[i] * self.radii[i];
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrtd = discriminant.sqrt();
+
+        // Find the nearest root that lies in the acceptable range.
+        let mut root = (-half_b - sqrtd) / a;
+        if !ray_t.surrounds(root) {
+            root = (-half_b + sqrtd) / a;
+            if !ray_t.surrounds(root) {
+                return None;
+            }
+        }
+        Some(root)
+    }
+}
+
+fn hit_box(bbox: &Aabb, origin: &Point3, inv_d: &Vec3, ray_t: &Interval) -> bool {
+    let mut t_min = ray_t.min;
+    let mut t_max = ray_t.max;
+    for a in 0..3 {
+        let axis = bbox.axis(a as i64);
+        let t0 = (axis.min - origin[a]) * inv_d[a];
+        let t1 = (axis.max - origin[a]) * inv_d[a];
+        t_min = f64::max(t_min, f64::min(t0, t1));
+        t_max = f64::min(t_max, f64::max(t0, t1));
+        if t_max <= t_min {
+            return false;
+        }
+    }
+    true
+}
+
+impl Hittable for SphereSoup {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let particles = &self.particles;
+        if particles.indices.is_empty() {
+            return None;
+        }
+        let inv_d = Vec3::from(
+            1.0 / r.direction().x(),
+            1.0 / r.direction().y(),
+            1.0 / r.direction().z(),
+        );
+
+        let mut closest_so_far = ray_t.max;
+        let mut hit_index = None;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let current = &particles.nodes[node];
+            let t_range = Interval::from(ray_t.min, closest_so_far);
+            if !hit_box(&current.bbox, r.origin(), &inv_d, &t_range) {
+                continue;
+            }
+            if current.count == 0 {
+                stack.push(current.first);
+                stack.push(current.first + 1);
+                continue;
+            }
+            for k in current.first..current.first + current.count {
+                let i = particles.indices[k] as usize;
+                let t_range = Interval::from(ray_t.min, closest_so_far);
+                if let Some(t) = particles.hit_particle(i, r, &t_range) {
+                    closest_so_far = t;
+                    hit_index = Some(i);
+                }
+            }
+        }
+        let i = hit_index?;
+
+        let t = closest_so_far;
+        let p = r.at(t);
+        let outward_normal = &(&p - &particles.centers[i]) / particles.radii[i];
+        let (u, v) = Sphere::get_sphere_uv(&outward_normal);
+        let mut rec = HitRecord {
+            t,
+            p,
+            normal: Vec3::blank(),
+            front_face: false,
+            u,
+            mat: self.mat.deref().clone(),
+            v,
+            color: particles.colors.as_ref().map(|c| c[i]),
+        };
+        rec.set_face_normal(r, &outward_normal);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.particles.nodes[0].bbox.clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        area_pdf_value(o, v, time, self.area(), |r, ray_t| self.hit(r, ray_t))
+    }
+
+    fn random(&self, o: &Vec3, _time: f64) -> Vec3 {
+        // Pick a particle in proportion to its area, then a uniform point on it.
+        let particles = &self.particles;
+        let pick = random_double() * self.area();
+        let i = usize::min(
+            particles.cumulative_areas.partition_point(|&a| a < pick),
+            particles.centers.len() - 1,
+        );
+        let p = &particles.centers[i] + &(particles.radii[i] * &Vec3::random_unit_vector());
+        &p - o
+    }
+
+    fn power(&self) -> f64 {
+        emitted_power(&self.mat, self.area())
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        beam_cone(&self.mat, LightCone::all())
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        if self.mat.is_emissive() && self.area() > 0.0 {
+            vec![Arc::new(self.clone())]
+        } else {
+            vec![]
+        }
+    }
+}
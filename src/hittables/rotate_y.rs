@@ -1,11 +1,13 @@
 use crate::hittables::hittable::{HitRecord, Hittable};
 use crate::math_structures::aabb::Aabb;
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::rtweekend::{degrees_to_radians, INFINITY};
 use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct RotateY {
     object: Arc<dyn Hittable + Send + Sync>,
     sin_theta: f64,
@@ -52,6 +54,22 @@ impl RotateY {
 
         this
     }
+
+    fn to_object(&self, v: &Vec3) -> Vec3 {
+        Vec3::from(
+            self.cos_theta * v.x() - self.sin_theta * v.z(),
+            v.y(),
+            self.sin_theta * v.x() + self.cos_theta * v.z(),
+        )
+    }
+
+    fn to_world(&self, v: &Vec3) -> Vec3 {
+        Vec3::from(
+            self.cos_theta * v.x() + self.sin_theta * v.z(),
+            v.y(),
+            -self.sin_theta * v.x() + self.cos_theta * v.z(),
+        )
+    }
 }
 
 impl Hittable for RotateY {
@@ -97,6 +115,35 @@ impl Hittable for RotateY {
     }
 
     fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
-        todo!()
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        // Rotations preserve solid angle, so the density is the object's density for the
+        // origin and direction expressed in object space.
+        self.object
+            .pdf_value(&self.to_object(o), &self.to_object(v), time)
+    }
+
+    fn random(&self, o: &Vec3, time: f64) -> Vec3 {
+        self.to_world(&self.object.random(&self.to_object(o), time))
+    }
+
+    fn power(&self) -> f64 {
+        self.object.power()
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        let cone = self.object.emission_cone();
+        LightCone::from(self.to_world(&cone.w), cone.theta_o, cone.theta_e)
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        let angle = f64::atan2(self.sin_theta, self.cos_theta).to_degrees();
+        self.object
+            .lights()
+            .into_iter()
+            .map(|l| Arc::new(RotateY::from(l, angle)) as Arc<dyn Hittable + Send + Sync>)
+            .collect()
     }
 }
@@ -1,8 +1,9 @@
 use crate::hittables::hittable::{HitRecord, Hittable};
 use crate::math_structures::aabb::Aabb;
 use crate::math_structures::interval::Interval;
+use crate::math_structures::light_bvh::LightCone;
 use crate::math_structures::ray::Ray;
-use crate::math_structures::vec3::Vec3;
+use crate::math_structures::vec3::{Point3, Vec3};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -51,4 +52,29 @@ impl Hittable for Translate {
     fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
         Box::new((*self).clone())
     }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        // Directions are unchanged by a translation, only the origin moves.
+        self.object.pdf_value(&(o - &self.offset), v, time)
+    }
+
+    fn random(&self, o: &Vec3, time: f64) -> Vec3 {
+        self.object.random(&(o - &self.offset), time)
+    }
+
+    fn power(&self) -> f64 {
+        self.object.power()
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        self.object.emission_cone()
+    }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        self.object
+            .lights()
+            .into_iter()
+            .map(|l| Arc::new(Translate::from(l, self.offset)) as Arc<dyn Hittable + Send + Sync>)
+            .collect()
+    }
 }
@@ -0,0 +1,71 @@
+use crate::hittables::hittable::{HitRecord, Hittable, LightSample};
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::Color;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{degrees_to_radians, random_double, INFINITY, PI};
+
+// A distant light such as the sun, arriving from `direction` with the same irradiance
+// everywhere. With a zero angular diameter it casts perfectly hard shadows; a small disk
+// (about 0.53 degrees for the sun) softens them. Either way it is only reached through shadow
+// rays.
+#[derive(Clone)]
+pub struct DirectionalLight {
+    uvw: Onb,
+    irradiance: Color,
+    cos_max: f64,
+}
+
+impl DirectionalLight {
+    pub fn from(direction: Vec3, irradiance: Color, angular_diameter: f64) -> DirectionalLight {
+        // `direction` points from the scene towards the light; `irradiance` is measured on a
+        // surface facing it.
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&direction);
+        DirectionalLight {
+            uvw,
+            irradiance,
+            cos_max: f64::cos(degrees_to_radians(angular_diameter / 2.0)),
+        }
+    }
+
+    fn sample_direction(&self) -> Vec3 {
+        // Uniform over the cone of directions covered by the light's disk.
+        if self.cos_max >= 1.0 {
+            return *self.uvw.w();
+        }
+        let z = 1.0 + random_double() * (self.cos_max - 1.0);
+        let r = f64::sqrt(f64::max(1.0 - z * z, 0.0));
+        let phi = 2.0 * PI * random_double();
+        self.uvw
+            .local_from_vec3(&Vec3::from(r * f64::cos(phi), r * f64::sin(phi), z))
+    }
+}
+
+impl Hittable for DirectionalLight {
+    fn hit(&self, _r: &Ray, _ray_t: &Interval) -> Option<HitRecord> {
+        None
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::blank()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn is_delta_light(&self) -> bool {
+        true
+    }
+
+    fn illuminate(&self, _p: &Point3, _time: f64) -> Option<LightSample> {
+        Some(LightSample {
+            direction: self.sample_direction(),
+            distance: INFINITY,
+            irradiance: self.irradiance,
+        })
+    }
+}
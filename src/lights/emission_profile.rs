@@ -0,0 +1,191 @@
+use crate::lights::ies::IesProfile;
+use crate::math_structures::color::Color;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{degrees_to_radians, PI};
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::texture::Texture;
+
+// How strongly a light emits in each direction, relative to its brightest direction. Every
+// profile but `Uniform` is aimed along an axis in world space, with `up` fixing how it is
+// turned about that axis.
+#[derive(Clone)]
+pub enum EmissionProfile {
+    Uniform,
+    // A cos^exponent falloff away from the axis.
+    CosinePower {
+        axis: Vec3,
+        exponent: f64,
+    },
+    // A slide projector or gobo: the image is thrown along the axis through a pinhole, filling
+    // `fov` degrees across, and nothing is emitted outside it.
+    Projector {
+        axis: Vec3,
+        right: Vec3,
+        up: Vec3,
+        tan_half_width: f64,
+        tan_half_height: f64,
+        image: Box<ImageTexture>,
+    },
+    // A measured distribution with the fixture pointing along the axis, so the file's nadir
+    // lies on it and its zero horizontal angle towards `up`.
+    Ies {
+        axis: Vec3,
+        right: Vec3,
+        up: Vec3,
+        profile: IesProfile,
+    },
+}
+
+impl EmissionProfile {
+    pub fn cosine_power(axis: Vec3, exponent: f64) -> EmissionProfile {
+        EmissionProfile::CosinePower {
+            axis: Vec3::unit_vector(&axis),
+            exponent,
+        }
+    }
+
+    pub fn projector(axis: Vec3, up: Vec3, fov: f64, image_filename: &str) -> EmissionProfile {
+        let (axis, right, up) = frame(&axis, &up);
+        let image = ImageTexture::from(image_filename);
+        let tan_half_width = f64::tan(degrees_to_radians(fov) / 2.0);
+        EmissionProfile::Projector {
+            axis,
+            right,
+            up,
+            tan_half_width,
+            tan_half_height: tan_half_width / image.aspect_ratio(),
+            image: Box::new(image),
+        }
+    }
+
+    pub fn ies(axis: Vec3, up: Vec3, ies_filename: &str) -> EmissionProfile {
+        let (axis, right, up) = frame(&axis, &up);
+        EmissionProfile::Ies {
+            axis,
+            right,
+            up,
+            profile: IesProfile::from_file(ies_filename),
+        }
+    }
+
+    pub fn beam(&self) -> Option<(Vec3, f64)> {
+        // The axis and half angle, in radians, of a cone holding every direction the profile
+        // emits in.
+        match self {
+            EmissionProfile::Uniform => None,
+            EmissionProfile::CosinePower { axis, .. } => Some((*axis, PI / 2.0)),
+            EmissionProfile::Projector {
+                axis,
+                tan_half_width,
+                tan_half_height,
+                ..
+            } => Some((
+                *axis,
+                f64::atan(f64::sqrt(
+                    tan_half_width * tan_half_width + tan_half_height * tan_half_height,
+                )),
+            )),
+            EmissionProfile::Ies { axis, profile, .. } => {
+                Some((*axis, degrees_to_radians(profile.spread())))
+            }
+        }
+    }
+
+    pub fn average(&self) -> Color {
+        // The power a surface facing along the axis gives off with this profile, relative to
+        // one emitting evenly in every direction.
+        let axis = match self.beam() {
+            None => return Color::from(1.0, 1.0, 1.0),
+            Some((axis, _)) => axis,
+        };
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&axis);
+        let steps = 256;
+        let step = 1.0 / steps as f64;
+        let mut sum = Color::blank();
+        for i in 0..steps {
+            let theta = (i as f64 + 0.5) * step * PI / 2.0;
+            let (sin_theta, cos_theta) = f64::sin_cos(theta);
+            for j in 0..steps {
+                let phi = (j as f64 + 0.5) * step * 2.0 * PI;
+                let d = uvw.local_from_vec3(&Vec3::from(
+                    sin_theta * f64::cos(phi),
+                    sin_theta * f64::sin(phi),
+                    cos_theta,
+                ));
+                sum += &(&self.surface_value(&d, &axis) * (cos_theta * sin_theta));
+            }
+        }
+        // The sum times the area of a step in theta and phi, over pi.
+        &sum * (step * step * PI)
+    }
+
+    pub fn surface_value(&self, direction: &Vec3, normal: &Vec3) -> Color {
+        // The relative radiance towards `direction` of a surface with the given normal. IES
+        // files give the intensity of the whole fixture, which spreads over the surface's area
+        // as seen from the direction, so it is divided by the cosine.
+        let value = self.value(direction);
+        match self {
+            EmissionProfile::Ies { .. } => {
+                let cos_theta = Vec3::dot(&Vec3::unit_vector(direction), normal);
+                &value / f64::max(cos_theta, 0.01)
+            }
+            _ => value,
+        }
+    }
+
+    pub fn value(&self, direction: &Vec3) -> Color {
+        // The relative emission towards `direction`, which points away from the light.
+        let d = Vec3::unit_vector(direction);
+        match self {
+            EmissionProfile::Uniform => Color::from(1.0, 1.0, 1.0),
+            EmissionProfile::CosinePower { axis, exponent } => {
+                let cos_theta = Vec3::dot(&d, axis);
+                if cos_theta <= 0.0 {
+                    return Color::blank();
+                }
+                let f = f64::powf(cos_theta, *exponent);
+                Color::from(f, f, f)
+            }
+            EmissionProfile::Projector {
+                axis,
+                right,
+                up,
+                tan_half_width,
+                tan_half_height,
+                image,
+            } => {
+                let z = Vec3::dot(&d, axis);
+                if z <= 0.0 {
+                    return Color::blank();
+                }
+                let s = 0.5 + Vec3::dot(&d, right) / (z * 2.0 * tan_half_width);
+                let t = 0.5 + Vec3::dot(&d, up) / (z * 2.0 * tan_half_height);
+                if !(0.0..=1.0).contains(&s) || !(0.0..=1.0).contains(&t) {
+                    return Color::blank();
+                }
+                image.value(s, t, &Point3::blank())
+            }
+            EmissionProfile::Ies {
+                axis,
+                right,
+                up,
+                profile,
+            } => {
+                let vertical = f64::acos(f64::clamp(Vec3::dot(&d, axis), -1.0, 1.0));
+                let horizontal = f64::atan2(Vec3::dot(&d, right), Vec3::dot(&d, up));
+                let f = profile.value(vertical * 180.0 / PI, horizontal * 180.0 / PI);
+                Color::from(f, f, f)
+            }
+        }
+    }
+}
+
+fn frame(axis: &Vec3, up: &Vec3) -> (Vec3, Vec3, Vec3) {
+    // Unit axis, right and up vectors, as for a camera looking along the axis.
+    let w = Vec3::unit_vector(axis);
+    let right = Vec3::unit_vector(&Vec3::cross(&w, up));
+    let up = Vec3::cross(&right, &w);
+    (w, right, up)
+}
@@ -0,0 +1,208 @@
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::{luminance, Color};
+use crate::math_structures::distribution::Distribution2D;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{degrees_to_radians, random_double, PI};
+use image::io::Reader as ImageReader;
+
+// A disk light such as the sun, too small to resolve in the image, added on top of it.
+#[derive(Clone)]
+struct SunDisk {
+    uvw: Onb,
+    cos_max: f64,
+    radiance: Color,
+    probability: f64,
+}
+
+// Light arriving from infinitely far away in every direction, looked up in an equirectangular
+// (latitude-longitude) HDR image. Directions are sampled in proportion to the brightness of
+// the image so small bright features such as studio softboxes and the sun are found easily.
+#[derive(Clone)]
+pub struct EnvironmentLight {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+    intensity: f64,
+    sin_rotation: f64,
+    cos_rotation: f64,
+    distribution: Distribution2D,
+    sun: Option<SunDisk>,
+}
+
+impl EnvironmentLight {
+    pub fn from_image(image_filename: &str, intensity: f64, rotation: f64) -> EnvironmentLight {
+        // Loads a linear HDR image (.hdr or .exr) from the data folder. The image is scaled by
+        // `intensity` and turned `rotation` degrees about the y axis.
+        let full_file_name = "./data/".to_string() + image_filename;
+        let img = match ImageReader::open(full_file_name).map(|r| r.decode()) {
+            Ok(Ok(x)) => x.to_rgb32f(),
+            Ok(Err(e)) => panic!("Couldn't Open Image: {}", e),
+            Err(e) => panic!("Couldn't Open Image: {}", e),
+        };
+        let width = img.width() as usize;
+        let height = img.height() as usize;
+        let pixels = img
+            .pixels()
+            .map(|p| Color::from(p[0] as f64, p[1] as f64, p[2] as f64))
+            .collect();
+        EnvironmentLight::from_pixels(width, height, pixels, intensity, rotation)
+    }
+
+    pub fn from_pixels(
+        width: usize,
+        height: usize,
+        pixels: Vec<Color>,
+        intensity: f64,
+        rotation: f64,
+    ) -> EnvironmentLight {
+        // Rows run from straight up to straight down. Each pixel is weighted by its brightness
+        // times the solid angle it covers, which shrinks towards the poles.
+        let mut weights = Vec::with_capacity(width * height);
+        for j in 0..height {
+            let sin_theta = f64::sin(PI * (j as f64 + 0.5) / height as f64);
+            for i in 0..width {
+                weights.push(luminance(&pixels[j * width + i]) * sin_theta);
+            }
+        }
+        let theta = degrees_to_radians(rotation);
+        EnvironmentLight {
+            width,
+            height,
+            pixels,
+            intensity,
+            sin_rotation: f64::sin(theta),
+            cos_rotation: f64::cos(theta),
+            distribution: Distribution2D::from(&weights, width, height),
+            sun: None,
+        }
+    }
+
+    pub fn with_sun(
+        mut self,
+        direction: Vec3,
+        radiance: Color,
+        angular_diameter: f64,
+    ) -> EnvironmentLight {
+        // Adds a disk of constant radiance centered on `direction`, which points from the scene
+        // towards it in world space, unaffected by the image rotation. The disk and the image
+        // are each sampled in proportion to the power they deliver, keeping a share for both.
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&direction);
+        let cos_max = f64::cos(degrees_to_radians(angular_diameter / 2.0));
+        let sun_power = luminance(&radiance) * 2.0 * PI * (1.0 - cos_max);
+        let image_power = 2.0 * PI * PI * self.distribution.integral();
+        let probability = if sun_power + image_power > 0.0 {
+            f64::clamp(sun_power / (sun_power + image_power), 0.05, 0.95)
+        } else {
+            0.5
+        };
+        self.sun = Some(SunDisk {
+            uvw,
+            cos_max,
+            radiance,
+            probability,
+        });
+        self
+    }
+
+    fn direction_to_uv(&self, direction: &Vec3) -> (f64, f64) {
+        // Undo the rotation, then map to longitude u and latitude v, both in [0, 1).
+        let d = Vec3::unit_vector(direction);
+        let x = self.cos_rotation * d.x() - self.sin_rotation * d.z();
+        let z = self.sin_rotation * d.x() + self.cos_rotation * d.z();
+        let theta = f64::acos(f64::clamp(d.y(), -1.0, 1.0));
+        let phi = f64::atan2(-z, x) + PI;
+        (
+            f64::min(phi / (2.0 * PI), 1.0 - 1e-12),
+            f64::min(theta / PI, 1.0 - 1e-12),
+        )
+    }
+
+    fn uv_to_direction(&self, u: f64, v: f64) -> Vec3 {
+        let phi = 2.0 * PI * u;
+        let theta = PI * v;
+        let x = -f64::cos(phi) * f64::sin(theta);
+        let z = f64::sin(phi) * f64::sin(theta);
+        Vec3::from(
+            self.cos_rotation * x + self.sin_rotation * z,
+            f64::cos(theta),
+            -self.sin_rotation * x + self.cos_rotation * z,
+        )
+    }
+
+    pub fn radiance(&self, direction: &Vec3) -> Color {
+        // Light arriving along the reverse of `direction`, i.e. seen when looking along it.
+        let (u, v) = self.direction_to_uv(direction);
+        let i = usize::min((u * self.width as f64) as usize, self.width - 1);
+        let j = usize::min((v * self.height as f64) as usize, self.height - 1);
+        let mut color = self.pixels[j * self.width + i];
+        if let Some(sun) = &self.sun {
+            if Vec3::dot(&Vec3::unit_vector(direction), sun.uvw.w()) >= sun.cos_max {
+                color += &sun.radiance;
+            }
+        }
+        &color * self.intensity
+    }
+
+    fn image_pdf(&self, v: &Vec3) -> f64 {
+        // The image pdf converted to solid angle; each pixel row spans 2*pi*pi*sin(theta).
+        let (u, t) = self.direction_to_uv(v);
+        let sin_theta = f64::sin(PI * t);
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+        self.distribution.pdf(u, t) / (2.0 * PI * PI * sin_theta)
+    }
+}
+
+impl Hittable for EnvironmentLight {
+    fn hit(&self, _r: &Ray, _ray_t: &Interval) -> Option<HitRecord> {
+        // Rays that escape the scene pick up its light through the camera instead.
+        None
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::blank()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, _o: &Point3, v: &Vec3, _time: f64) -> f64 {
+        match &self.sun {
+            None => self.image_pdf(v),
+            Some(sun) => {
+                let in_disk = Vec3::dot(&Vec3::unit_vector(v), sun.uvw.w()) >= sun.cos_max;
+                let disk_pdf = if in_disk {
+                    1.0 / (2.0 * PI * (1.0 - sun.cos_max))
+                } else {
+                    0.0
+                };
+                sun.probability * disk_pdf + (1.0 - sun.probability) * self.image_pdf(v)
+            }
+        }
+    }
+
+    fn random(&self, _o: &Vec3, _time: f64) -> Vec3 {
+        if let Some(sun) = &self.sun {
+            if random_double() < sun.probability {
+                // Uniform over the cone of directions covered by the disk.
+                let z = 1.0 + random_double() * (sun.cos_max - 1.0);
+                let r = f64::sqrt(f64::max(1.0 - z * z, 0.0));
+                let phi = 2.0 * PI * random_double();
+                return sun.uvw.local_from_vec3(&Vec3::from(
+                    r * f64::cos(phi),
+                    r * f64::sin(phi),
+                    z,
+                ));
+            }
+        }
+        let (u, v) = self.distribution.sample(random_double(), random_double());
+        self.uv_to_direction(u, v)
+    }
+}
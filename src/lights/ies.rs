@@ -0,0 +1,143 @@
+use std::fs;
+
+// A measured luminaire intensity distribution read from an IES LM-63 photometric file, in
+// type C photometry: vertical angles run from 0 degrees straight down the fixture axis to
+// 180 straight up, horizontal angles turn around the axis. Values are relative candela,
+// scaled so the brightest direction is 1.
+#[derive(Clone)]
+pub struct IesProfile {
+    vertical_angles: Vec<f64>,
+    horizontal_angles: Vec<f64>,
+    candela: Vec<f64>,
+}
+
+impl IesProfile {
+    pub fn from_file(filename: &str) -> IesProfile {
+        // Loads an .ies file from the data folder.
+        let full_file_name = "./data/".to_string() + filename;
+        let text = match fs::read_to_string(&full_file_name) {
+            Ok(x) => x,
+            Err(e) => panic!("Couldn't Open IES File {}: {}", full_file_name, e),
+        };
+        match IesProfile::parse(&text) {
+            Some(x) => x,
+            None => panic!("Couldn't Parse IES File {}", full_file_name),
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<IesProfile> {
+        // The header is free text up to the TILT line; everything after it is numbers
+        // separated by spaces, commas or line breaks.
+        let mut lines = text.lines();
+        let tilt = lines.find(|l| l.trim_start().starts_with("TILT="))?;
+        let mut numbers = lines
+            .flat_map(|l| l.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f64>().ok());
+        let mut next = || numbers.next().flatten();
+
+        if tilt.trim() == "TILT=INCLUDE" {
+            // Lamp-to-luminaire geometry, then the tilt angles and their multipliers. Tilt
+            // only matters for lamps that change output with orientation; it is ignored.
+            next()?;
+            let count = next()? as usize;
+            for _ in 0..2 * count {
+                next()?;
+            }
+        }
+
+        let _lamps = next()?;
+        let _lumens_per_lamp = next()?;
+        let multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()?;
+        // Units, luminous opening width, length and height, ballast factor, a reserved value
+        // and input watts are not needed.
+        for _ in 0..7 {
+            next()?;
+        }
+        if photometric_type != 1.0 || vertical_count == 0 || horizontal_count == 0 {
+            return None;
+        }
+
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next())
+            .collect::<Option<Vec<f64>>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next())
+            .collect::<Option<Vec<f64>>>()?;
+        let mut candela = (0..vertical_count * horizontal_count)
+            .map(|_| next().map(|c| f64::max(c * multiplier, 0.0)))
+            .collect::<Option<Vec<f64>>>()?;
+
+        let peak = candela.iter().cloned().fold(0.0, f64::max);
+        if peak > 0.0 {
+            for c in candela.iter_mut() {
+                *c /= peak;
+            }
+        }
+        Some(IesProfile {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+        })
+    }
+
+    pub fn spread(&self) -> f64 {
+        // The widest vertical angle, in degrees, the fixture gives any light at.
+        let n = self.vertical_angles.len();
+        let lit =
+            |i: usize| (0..self.horizontal_angles.len()).any(|h| self.candela[h * n + i] > 0.0);
+        match (0..n).rev().find(|&i| lit(i)) {
+            // Light fades out by the next angle measured.
+            Some(i) => self.vertical_angles[usize::min(i + 1, n - 1)],
+            None => 0.0,
+        }
+    }
+
+    pub fn value(&self, vertical: f64, horizontal: f64) -> f64 {
+        // Relative intensity at a vertical angle in [0, 180] and a horizontal angle, both in
+        // degrees, interpolated bilinearly. Files only list the horizontal angles their
+        // symmetry needs, so the angle is folded into the range they cover first.
+        let last = *self.horizontal_angles.last().unwrap();
+        let mut h = horizontal.rem_euclid(360.0);
+        if last == 0.0 {
+            h = 0.0;
+        } else if last == 90.0 {
+            h %= 180.0;
+            if h > 90.0 {
+                h = 180.0 - h;
+            }
+        } else if last == 180.0 && h > 180.0 {
+            h = 360.0 - h;
+        }
+
+        let first = *self.vertical_angles.first().unwrap();
+        if vertical < first || vertical > *self.vertical_angles.last().unwrap() {
+            // Outside the measured range the fixture gives no light, e.g. above a downlight.
+            return 0.0;
+        }
+        let (v0, v1, tv) = bracket(&self.vertical_angles, vertical);
+        let (h0, h1, th) = bracket(&self.horizontal_angles, h);
+        let row = |i: usize| {
+            let start = i * self.vertical_angles.len();
+            (1.0 - tv) * self.candela[start + v0] + tv * self.candela[start + v1]
+        };
+        (1.0 - th) * row(h0) + th * row(h1)
+    }
+}
+
+fn bracket(angles: &[f64], x: f64) -> (usize, usize, f64) {
+    // The two entries of a sorted list on either side of x and how far x is between them,
+    // clamping to the ends.
+    let i = angles.partition_point(|&a| a <= x);
+    if i == 0 {
+        return (0, 0, 0.0);
+    }
+    if i == angles.len() {
+        return (i - 1, i - 1, 0.0);
+    }
+    let t = (x - angles[i - 1]) / (angles[i] - angles[i - 1]);
+    (i - 1, i, t)
+}
@@ -0,0 +1,7 @@
+pub mod directional_light;
+pub mod emission_profile;
+pub mod environment_light;
+pub mod ies;
+pub mod point_light;
+pub mod sky;
+pub mod spot_light;
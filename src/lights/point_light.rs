@@ -0,0 +1,72 @@
+use crate::hittables::hittable::{HitRecord, Hittable, LightSample};
+use crate::lights::emission_profile::EmissionProfile;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::Color;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::Point3;
+
+// A light at a single point, shining equally in every direction. It has no surface, so it
+// only lights the scene through shadow rays and is never seen directly.
+#[derive(Clone)]
+pub struct PointLight {
+    position: Point3,
+    intensity: Color,
+    profile: EmissionProfile,
+}
+
+impl PointLight {
+    pub fn from(position: Point3, intensity: Color) -> PointLight {
+        // Intensity is the power per unit solid angle; irradiance falls off with the squared
+        // distance.
+        PointLight {
+            position,
+            intensity,
+            profile: EmissionProfile::Uniform,
+        }
+    }
+
+    pub fn with_profile(mut self, profile: EmissionProfile) -> PointLight {
+        // Shapes the light by direction; `intensity` becomes the intensity along the
+        // profile's brightest direction.
+        self.profile = profile;
+        self
+    }
+}
+
+impl Hittable for PointLight {
+    fn hit(&self, _r: &Ray, _ray_t: &Interval) -> Option<HitRecord> {
+        None
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&self.position, &self.position)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn is_delta_light(&self) -> bool {
+        true
+    }
+
+    fn illuminate(&self, p: &Point3, _time: f64) -> Option<LightSample> {
+        let to_light = &self.position - p;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= 0.0 {
+            return None;
+        }
+        let distance = f64::sqrt(distance_squared);
+        let direction = &to_light / distance;
+        let intensity = &self.intensity * &self.profile.value(&-&direction);
+        if intensity.length_squared() <= 0.0 {
+            return None;
+        }
+        Some(LightSample {
+            direction,
+            distance,
+            irradiance: &intensity / distance_squared,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+use crate::lights::environment_light::EnvironmentLight;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+use crate::rtweekend::{degrees_to_radians, PI};
+
+// Angular diameter of the sun in degrees.
+const SUN_ANGULAR_DIAMETER: f64 = 0.53;
+
+// Luminance of the sun outside the atmosphere, in kcd/m^2 like the sky.
+const SUN_LUMINANCE: f64 = 2.0e6;
+
+// Analytic daylight sky after Preetham, Shirley and Smits, "A Practical Analytic Model for
+// Daylight". The sky is set by where the sun is and how hazy the air is: turbidity 2 is a
+// very clear day, around 3 a clear one and 6 or more a hazy one. Radiance is in kcd/m^2, so
+// scenes scale it down to their exposure with an intensity.
+#[derive(Clone)]
+pub struct PreethamSky {
+    sun: Vec3,
+    theta_s: f64,
+    turbidity: f64,
+    perez_luminance: [f64; 5],
+    perez_x: [f64; 5],
+    perez_y: [f64; 5],
+    zenith: (f64, f64, f64),
+}
+
+impl PreethamSky {
+    pub fn from(sun_elevation: f64, sun_azimuth: f64, turbidity: f64) -> PreethamSky {
+        // Elevation is in degrees above the horizon. Azimuth is in degrees from -z (straight
+        // ahead of a default camera) turning towards +x.
+        let elevation = degrees_to_radians(f64::clamp(sun_elevation, 0.0, 90.0));
+        let azimuth = degrees_to_radians(sun_azimuth);
+        let sun = Vec3::from(
+            f64::sin(azimuth) * f64::cos(elevation),
+            f64::sin(elevation),
+            -f64::cos(azimuth) * f64::cos(elevation),
+        );
+        let t = turbidity;
+        let theta_s = PI / 2.0 - elevation;
+
+        let perez_luminance = [
+            0.1787 * t - 1.4630,
+            -0.3554 * t + 0.4275,
+            -0.0227 * t + 5.3251,
+            0.1206 * t - 2.5771,
+            -0.0670 * t + 0.3703,
+        ];
+        let perez_x = [
+            -0.0193 * t - 0.2592,
+            -0.0665 * t + 0.0008,
+            -0.0004 * t + 0.2125,
+            -0.0641 * t - 0.8989,
+            -0.0033 * t + 0.0452,
+        ];
+        let perez_y = [
+            -0.0167 * t - 0.2608,
+            -0.0950 * t + 0.0092,
+            -0.0079 * t + 0.2102,
+            -0.0441 * t - 1.6537,
+            -0.0109 * t + 0.0529,
+        ];
+
+        // Zenith luminance and chromaticity, fitted in terms of turbidity and the sun's zenith
+        // angle.
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * theta_s);
+        let zenith_luminance = (4.0453 * t - 4.9710) * f64::tan(chi) - 0.2155 * t + 2.4192;
+        let th = [theta_s * theta_s * theta_s, theta_s * theta_s, theta_s, 1.0];
+        let dot = |c: [f64; 4]| c[0] * th[0] + c[1] * th[1] + c[2] * th[2] + c[3] * th[3];
+        let zenith_x = t * t * dot([0.00166, -0.00375, 0.00209, 0.0])
+            + t * dot([-0.02903, 0.06377, -0.03202, 0.00394])
+            + dot([0.11693, -0.21196, 0.06052, 0.25886]);
+        let zenith_y = t * t * dot([0.00275, -0.00610, 0.00317, 0.0])
+            + t * dot([-0.04214, 0.08970, -0.04153, 0.00516])
+            + dot([0.15346, -0.26756, 0.06670, 0.26688]);
+
+        PreethamSky {
+            sun,
+            theta_s,
+            turbidity,
+            perez_luminance,
+            perez_x,
+            perez_y,
+            zenith: (f64::max(zenith_luminance, 0.0), zenith_x, zenith_y),
+        }
+    }
+
+    pub fn radiance(&self, direction: &Vec3) -> Color {
+        // Sky radiance seen looking along `direction`, without the sun disk. Below the horizon
+        // the sky model does not apply; there the horizon is seen darkened by a grey ground.
+        let d = Vec3::unit_vector(direction);
+        let ground = if d.y() < 0.0 { 0.3 } else { 1.0 };
+        let d = if d.y() < 0.001 {
+            Vec3::unit_vector(&Vec3::from(d.x(), 0.001, d.z()))
+        } else {
+            d
+        };
+        let cos_theta = d.y();
+        let gamma = f64::acos(f64::clamp(Vec3::dot(&d, &self.sun), -1.0, 1.0));
+
+        let luminance = self.zenith.0 * self.perez_ratio(&self.perez_luminance, cos_theta, gamma);
+        let x = self.zenith.1 * self.perez_ratio(&self.perez_x, cos_theta, gamma);
+        let y = self.zenith.2 * self.perez_ratio(&self.perez_y, cos_theta, gamma);
+        &xyy_to_rgb(x, y, luminance) * ground
+    }
+
+    pub fn sun_radiance(&self) -> Color {
+        // Sunlight dimmed along its path through the air by Rayleigh scattering and by aerosols,
+        // the latter growing with turbidity. Evaluated at 650, 550 and 450 nm for red, green
+        // and blue.
+        let theta_deg = self.theta_s.to_degrees();
+        let air_mass =
+            1.0 / (f64::cos(self.theta_s) + 0.50572 * f64::powf(96.07995 - theta_deg, -1.6364));
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |lambda: f64| {
+            let rayleigh = 0.008735 * f64::powf(lambda, -4.08);
+            let aerosol = beta * f64::powf(lambda, -1.3);
+            f64::exp(-(rayleigh + aerosol) * air_mass)
+        };
+        &Color::from(
+            transmittance(0.65),
+            transmittance(0.55),
+            transmittance(0.45),
+        ) * SUN_LUMINANCE
+    }
+
+    pub fn environment(&self, width: usize, height: usize, intensity: f64) -> EnvironmentLight {
+        // Tabulates the sky into an environment light with the sun disk on top, so both are
+        // seen by escaped rays and importance sampled.
+        let mut pixels = Vec::with_capacity(width * height);
+        for j in 0..height {
+            let theta = PI * (j as f64 + 0.5) / height as f64;
+            for i in 0..width {
+                // The same latitude-longitude layout EnvironmentLight uses.
+                let phi = 2.0 * PI * (i as f64 + 0.5) / width as f64;
+                pixels.push(self.radiance(&Vec3::from(
+                    -f64::cos(phi) * f64::sin(theta),
+                    f64::cos(theta),
+                    f64::sin(phi) * f64::sin(theta),
+                )));
+            }
+        }
+        EnvironmentLight::from_pixels(width, height, pixels, intensity, 0.0).with_sun(
+            self.sun,
+            self.sun_radiance(),
+            SUN_ANGULAR_DIAMETER,
+        )
+    }
+
+    fn perez_ratio(&self, c: &[f64; 5], cos_theta: f64, gamma: f64) -> f64 {
+        // The Perez sky distribution at (theta, gamma) relative to its value at the zenith.
+        let perez = |cos_theta: f64, gamma: f64| {
+            (1.0 + c[0] * f64::exp(c[1] / cos_theta))
+                * (1.0 + c[2] * f64::exp(c[3] * gamma) + c[4] * f64::cos(gamma).powi(2))
+        };
+        perez(cos_theta, gamma) / perez(1.0, self.theta_s)
+    }
+}
+
+fn xyy_to_rgb(x: f64, y: f64, luminance: f64) -> Color {
+    // CIE xyY to linear sRGB, dropping colors outside the gamut.
+    if y <= 0.0 {
+        return Color::from(0.0, 0.0, 0.0);
+    }
+    let cx = x * luminance / y;
+    let cz = (1.0 - x - y) * luminance / y;
+    Color::from(
+        f64::max(3.2406 * cx - 1.5372 * luminance - 0.4986 * cz, 0.0),
+        f64::max(-0.9689 * cx + 1.8758 * luminance + 0.0415 * cz, 0.0),
+        f64::max(0.0557 * cx - 0.2040 * luminance + 1.0570 * cz, 0.0),
+    )
+}
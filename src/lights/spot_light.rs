@@ -0,0 +1,87 @@
+use crate::hittables::hittable::{HitRecord, Hittable, LightSample};
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::color::Color;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::degrees_to_radians;
+
+// A point light restricted to a cone. Full intensity inside `falloff_start` degrees of the
+// axis, fading smoothly to nothing at `total_width` degrees.
+#[derive(Clone)]
+pub struct SpotLight {
+    position: Point3,
+    axis: Vec3,
+    intensity: Color,
+    cos_total_width: f64,
+    cos_falloff_start: f64,
+}
+
+impl SpotLight {
+    pub fn from(
+        position: Point3,
+        look_at: Point3,
+        intensity: Color,
+        total_width: f64,
+        falloff_start: f64,
+    ) -> SpotLight {
+        let falloff_start = f64::min(falloff_start, total_width);
+        SpotLight {
+            position,
+            axis: Vec3::unit_vector(&(&look_at - &position)),
+            intensity,
+            cos_total_width: f64::cos(degrees_to_radians(total_width)),
+            cos_falloff_start: f64::cos(degrees_to_radians(falloff_start)),
+        }
+    }
+
+    fn falloff(&self, cos_theta: f64) -> f64 {
+        // Smoothstep between the edge of the cone and the start of the falloff.
+        if cos_theta <= self.cos_total_width {
+            return 0.0;
+        }
+        if cos_theta >= self.cos_falloff_start {
+            return 1.0;
+        }
+        let t =
+            (cos_theta - self.cos_total_width) / (self.cos_falloff_start - self.cos_total_width);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Hittable for SpotLight {
+    fn hit(&self, _r: &Ray, _ray_t: &Interval) -> Option<HitRecord> {
+        None
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&self.position, &self.position)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn is_delta_light(&self) -> bool {
+        true
+    }
+
+    fn illuminate(&self, p: &Point3, _time: f64) -> Option<LightSample> {
+        let to_light = &self.position - p;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= 0.0 {
+            return None;
+        }
+        let distance = f64::sqrt(distance_squared);
+        let direction = &to_light / distance;
+        let falloff = self.falloff(-Vec3::dot(&direction, &self.axis));
+        if falloff <= 0.0 {
+            return None;
+        }
+        Some(LightSample {
+            direction,
+            distance,
+            irradiance: &self.intensity * (falloff / distance_squared),
+        })
+    }
+}
@@ -1,5 +1,7 @@
+mod backgrounds;
 mod camera;
 mod hittables;
+mod lights;
 mod materials;
 mod math_structures;
 mod rtw_image;
@@ -15,19 +17,39 @@ mod winsdl;
 
 use scenes::chat_scene::ChatScene;
 use scenes::jay_patel_scene::JayPatelScene;
+use scenes::lathe_scene::LatheScene;
 use scenes::purple_cornell_box_scene::PurpleCornellBoxScene;
 
 use crate::live_render::show_screen;
 use crate::multithreading::render_to_memory;
+use crate::scenes::background_scene::BackgroundScene;
+use crate::scenes::city_lights_scene::CityLightsScene;
+use crate::scenes::cloth_scene::ClothScene;
+use crate::scenes::coated_scene::CoatedScene;
+use crate::scenes::color_temperature_scene::ColorTemperatureScene;
 use crate::scenes::cornell_box_scene::CornellBoxScene;
 use crate::scenes::cornell_smoke_scene::CornellSmokeScene;
+use crate::scenes::delta_lights_scene::DeltaLightsScene;
 use crate::scenes::diff_final_scene::DiffFinalScene;
 use crate::scenes::earth_in_a_ball::EarthInABallScene;
 use crate::scenes::earth_scene::EarthScene;
 use crate::scenes::final_scene::FinalScene;
+use crate::scenes::fixtures_scene::FixturesScene;
+use crate::scenes::frosted_glass_scene::FrostedGlassScene;
+use crate::scenes::metals_scene::MetalsScene;
+use crate::scenes::mix_scene::MixScene;
+use crate::scenes::nested_dielectrics_scene::NestedDielectricsScene;
+use crate::scenes::particle_scene::ParticleScene;
+use crate::scenes::primitives_scene::PrimitivesScene;
+use crate::scenes::principled_scene::PrincipledScene;
 use crate::scenes::quads_scene::QuadsScene;
 use crate::scenes::random_spheres_scene::RandomSpheresScene;
+use crate::scenes::rough_diffuse_scene::RoughDiffuseScene;
 use crate::scenes::simple_list_scene::SimpleListScene;
+use crate::scenes::studio_scene::StudioScene;
+use crate::scenes::subsurface_scene::SubsurfaceScene;
+use crate::scenes::sun_sky_scene::SunSkyScene;
+use crate::scenes::thin_film_scene::ThinFilmScene;
 use crate::scenes::two_perlin_spheres_scene::TwoPerlinSpheresScene;
 use crate::scenes::two_spheres_scene::TwoSpheresScene;
 use crate::scenes::Scene;
@@ -88,6 +110,72 @@ fn main() {
         14 => {
             scene = Box::new(JayPatelScene::blank());
         }
+        15 => {
+            scene = Box::new(LatheScene::blank());
+        }
+        16 => {
+            scene = Box::new(PrimitivesScene::blank());
+        }
+        17 => {
+            scene = Box::new(ParticleScene::blank());
+        }
+        18 => {
+            scene = Box::new(CityLightsScene::blank());
+        }
+        19 => {
+            scene = Box::new(DeltaLightsScene::blank());
+        }
+        20 => {
+            scene = Box::new(StudioScene::blank());
+        }
+        21 => {
+            scene = Box::new(SunSkyScene::blank());
+        }
+        22 => {
+            scene = Box::new(ColorTemperatureScene::blank());
+        }
+        23 => {
+            scene = Box::new(FixturesScene::blank());
+        }
+        24 => {
+            scene = Box::new(MetalsScene::blank());
+        }
+        25 => {
+            scene = Box::new(FrostedGlassScene::blank());
+        }
+        26 => {
+            scene = Box::new(NestedDielectricsScene::blank());
+        }
+        27 => {
+            scene = Box::new(PrincipledScene::blank());
+        }
+        28 => {
+            scene = Box::new(CoatedScene::blank());
+        }
+        29 => {
+            scene = Box::new(RoughDiffuseScene::blank());
+        }
+        30 => {
+            scene = Box::new(SubsurfaceScene::blank());
+        }
+        31 => {
+            scene = Box::new(ThinFilmScene::blank());
+        }
+        32 => {
+            scene = Box::new(ClothScene::blank());
+        }
+        33 => {
+            scene = Box::new(MixScene::blank());
+        }
+        34 => {
+            scene = Box::new(BackgroundScene::sky());
+        }
+        35 => {
+            scene = Box::new(BackgroundScene::panorama());
+        }
+        36 => {
+            scene = Box::new(BackgroundScene::checkered());
+        }
         _ => {
             panic!("Invalid Scene Selected")
         }
@@ -137,5 +225,27 @@ const SCENE_LIST: &'static str = " 1 => Quads Scene
 11 => Different Final Scene (Kinda Slow)
 12 => Purple Cornell Box Scene
 13 => Chat Scene
-14 => Jay Patel??\
+14 => Jay Patel??
+15 => Lathe And Extrude Scene
+16 => Primitives Scene
+17 => Particle Scene
+18 => City Lights Scene
+19 => Delta Lights Scene
+20 => Studio Scene
+21 => Sun And Sky Scene
+22 => Color Temperature Scene
+23 => Light Fixtures Scene
+24 => Metals Scene
+25 => Frosted Glass Scene
+26 => Nested Dielectrics Scene
+27 => Principled Scene
+28 => Coated Scene
+29 => Rough Diffuse Scene
+30 => Subsurface Scene
+31 => Thin Film Scene
+32 => Cloth Scene
+33 => Mix Scene
+34 => Sky Background Scene
+35 => Panorama Background Scene
+36 => Checkered Background Scene\
 ";
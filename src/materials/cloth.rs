@@ -0,0 +1,194 @@
+use crate::hittables::hittable::HitRecord;
+use crate::materials::material::{Material, ScatterRecord};
+use crate::materials::microfacet::charlie_d;
+use crate::materials::principled::constant;
+use crate::math_structures::color::{luminance, Color};
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::charlie_pdf::CharliePdf;
+use crate::pdf::cosine_pdf::CosinePdf;
+use crate::pdf::mixture_pdf::MixturePdf;
+use crate::rtweekend::PI;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+use crate::textures::TexEnum;
+
+// The fraction of the light arriving from one direction that a white sheen reflects, found by
+// integrating its BRDF numerically. Rows are alphas from 0.1 to 1 in steps of 0.15 and columns
+// the cosine of the direction seen from, from 0 to 1 in steps of 0.125.
+const SHEEN_ALBEDO: [[f64; 9]; 7] = [
+    [
+        1.352, 0.703, 0.424, 0.255, 0.148, 0.080, 0.038, 0.014, 0.002,
+    ],
+    [
+        0.937, 0.596, 0.431, 0.316, 0.230, 0.162, 0.108, 0.065, 0.031,
+    ],
+    [
+        0.800, 0.546, 0.420, 0.329, 0.257, 0.198, 0.147, 0.103, 0.065,
+    ],
+    [
+        0.730, 0.517, 0.411, 0.333, 0.271, 0.218, 0.171, 0.129, 0.092,
+    ],
+    [
+        0.687, 0.497, 0.404, 0.335, 0.279, 0.230, 0.188, 0.149, 0.112,
+    ],
+    [
+        0.658, 0.484, 0.398, 0.335, 0.284, 0.239, 0.199, 0.163, 0.129,
+    ],
+    [
+        0.636, 0.473, 0.394, 0.335, 0.287, 0.246, 0.208, 0.174, 0.141,
+    ],
+];
+
+// Fabric such as velvet, felt or cotton: a diffuse base under a sheen lobe, the soft glow of
+// fibres catching the light at grazing angles. The sheen uses the Charlie distribution with
+// the visibility term of Neubelt and Pettineo, "Crafting a Next-Gen Material Pipeline for The
+// Order: 1886". Roughness is read from the red channel of its texture.
+#[derive(Clone)]
+pub struct Cloth {
+    albedo: Box<TexEnum>,
+    sheen: Box<TexEnum>,
+    roughness: Box<TexEnum>,
+    threads: Option<f64>,
+}
+
+impl Cloth {
+    pub fn from(albedo: TexEnum, sheen: TexEnum) -> Cloth {
+        Cloth {
+            albedo: Box::new(albedo),
+            sheen: Box::new(sheen),
+            roughness: Box::new(constant(0.5)),
+            threads: None,
+        }
+    }
+    pub fn from_color(albedo: Color, sheen: Color) -> Cloth {
+        Cloth::from(
+            TexEnum::SolidColor(SolidColor::from(albedo)),
+            TexEnum::SolidColor(SolidColor::from(sheen)),
+        )
+    }
+    pub fn with_roughness(mut self, roughness: TexEnum) -> Cloth {
+        // Low roughness is velvet, whose sheen hugs the silhouette; high roughness is a softer,
+        // broader glow like brushed cotton.
+        self.roughness = Box::new(roughness);
+        self
+    }
+    pub fn with_plain_weave(mut self, threads: f64) -> Cloth {
+        // Woven in a plain weave with `threads` threads each way across the texture.
+        self.threads = Some(threads);
+        self
+    }
+
+    fn alpha(&self, rec: &HitRecord) -> f64 {
+        // `SHEEN_ALBEDO` only goes down to 0.1, and smoother sheens are too sharp to sample.
+        let roughness = self.roughness.value(rec.u, rec.v, &rec.p).x();
+        f64::clamp(roughness * roughness, 0.1, 1.0)
+    }
+
+    fn weave(&self, rec: &HitRecord) -> f64 {
+        // How lit the cloth is at the hit point. In a plain weave the threads running along v
+        // and along u take turns on top in a checkerboard. Each is rounded across its width
+        // and dips at the ends where it goes under its neighbours, and the gaps are in shadow.
+        let threads = match self.threads {
+            None => return 1.0,
+            Some(x) => x,
+        };
+        let s = rec.u * threads;
+        let t = rec.v * threads;
+        let along_v_on_top = (s.floor() + t.floor()).rem_euclid(2.0) < 1.0;
+        let (across, along) = if along_v_on_top {
+            (s.fract(), t.fract())
+        } else {
+            (t.fract(), s.fract())
+        };
+        let x = 2.0 * f64::abs(across) - 1.0;
+        let y = 2.0 * f64::abs(along) - 1.0;
+        let profile = f64::sqrt(f64::max(0.0, 1.0 - x * x)) * (1.0 - 0.4 * y * y);
+        0.3 + 0.7 * profile
+    }
+
+    fn bsdf(&self, albedo: &Color, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        // The BSDF times the cosine, over a base of the given color.
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.vec3_to_local(&Vec3::unit_vector(scattered.direction()));
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return Color::blank();
+        }
+        let sheen = self.sheen.value(rec.u, rec.v, &rec.p);
+        let alpha = self.alpha(rec);
+
+        // Near grazing angles a smooth sheen's BRDF integrates to more than 1, so it is scaled
+        // down to reflect at most the light that arrives.
+        let reflected = sheen_albedo(wo.z(), alpha);
+        let wm = Vec3::unit_vector(&(&wo + &wi));
+        let visibility = 1.0 / (4.0 * (wi.z() + wo.z() - wi.z() * wo.z()));
+        let fibres =
+            &sheen * (charlie_d(alpha, &wm) * visibility * wi.z() / f64::max(reflected, 1.0));
+
+        // The light the sheen reflects never reaches the base.
+        let strongest = f64::max(sheen.x(), f64::max(sheen.y(), sheen.z()));
+        let base_left = f64::max(0.0, 1.0 - strongest * f64::min(reflected, 1.0));
+        let diffuse = albedo * (base_left * wi.z() / PI);
+
+        &(&diffuse + &fibres) * self.weave(rec)
+    }
+}
+
+impl Material for Cloth {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let albedo = self.albedo.value(rec.u, rec.v, &rec.p);
+        let sheen = self.sheen.value(rec.u, rec.v, &rec.p);
+        let mut srec = ScatterRecord::blank();
+        srec.attenuation = rec.tint(Color::from(1.0, 1.0, 1.0));
+        srec.pdf_ptr = Box::new(MixturePdf::weighted(vec![
+            (luminance(&albedo), Box::new(CosinePdf::from(&rec.normal))),
+            (
+                f64::max(luminance(&sheen), 0.1),
+                Box::new(CharliePdf::from(uvw, wo, self.alpha(rec))),
+            ),
+        ]));
+        srec.skip_pdf = false;
+        Some(srec)
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // The luminance of the BSDF times the cosine over a white base, for callers that need a
+        // single number.
+        luminance(&self.bsdf(&Color::from(1.0, 1.0, 1.0), r_in, rec, scattered))
+    }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        let albedo = self.albedo.value(rec.u, rec.v, &rec.p);
+        attenuation * &self.bsdf(&albedo, r_in, rec, scattered)
+    }
+}
+
+fn sheen_albedo(cos_theta: f64, alpha: f64) -> f64 {
+    // Bilinear lookup in `SHEEN_ALBEDO`.
+    let row = f64::clamp((alpha - 0.1) / 0.15, 0.0, 5.999);
+    let column = f64::clamp(cos_theta * 8.0, 0.0, 7.999);
+    let (i, j) = (row as usize, column as usize);
+    let (s, t) = (row.fract(), column.fract());
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+    lerp(
+        lerp(SHEEN_ALBEDO[i][j], SHEEN_ALBEDO[i][j + 1], t),
+        lerp(SHEEN_ALBEDO[i + 1][j], SHEEN_ALBEDO[i + 1][j + 1], t),
+        s,
+    )
+}
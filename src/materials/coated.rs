@@ -0,0 +1,188 @@
+use crate::hittables::hittable::HitRecord;
+use crate::materials::material::{Material, ScatterRecord};
+use crate::materials::microfacet::{dielectric_reflection, fresnel_dielectric, TrowbridgeReitz};
+use crate::materials::MatEnum;
+use crate::math_structures::color::{luminance, Color};
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{reflect, Point3, Vec3};
+use crate::pdf::ggx_pdf::GgxPdf;
+use crate::pdf::mixture_pdf::MixturePdf;
+use crate::pdf::pdf::Pdf;
+use crate::rtweekend::random_double;
+
+// A thin clear layer such as lacquer or varnish over another material. Light reflects off the
+// top of the coat by its Fresnel reflectance; the rest passes through, is tinted by the coat on
+// its way down to the base and back up, and leaves through the top again. The coat is thin
+// enough that the base is seen where it is hit.
+#[derive(Clone)]
+pub struct Coated {
+    base: Box<MatEnum>,
+    ior: f64,
+    distribution: TrowbridgeReitz,
+    tint: Color,
+    thickness: f64,
+}
+
+impl Coated {
+    pub fn from(base: MatEnum, ior: f64, roughness: f64) -> Coated {
+        Coated {
+            base: Box::new(base),
+            ior,
+            distribution: TrowbridgeReitz::from(roughness, 0.0),
+            tint: Color::from(1.0, 1.0, 1.0),
+            thickness: 1.0,
+        }
+    }
+    pub fn with_tint(mut self, tint: Color) -> Coated {
+        // The color the coat gives a white base seen head on, like the amber of old varnish.
+        self.tint = tint;
+        self
+    }
+    pub fn with_thickness(mut self, thickness: f64) -> Coated {
+        // How many times thicker than the one `with_tint` describes the coat is.
+        self.thickness = thickness;
+        self
+    }
+
+    fn frame(&self, r_in: &Ray, rec: &HitRecord) -> (Onb, Vec3) {
+        // The local frame of the normal and the incoming ray reversed in it.
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        (uvw, wo)
+    }
+
+    fn one_way(&self, cos_theta: f64) -> Color {
+        // The fraction of light getting through the top of the coat at cos_theta and across to
+        // the base, or back the other way, after the Fresnel loss and absorption along the
+        // refracted path.
+        if cos_theta <= 0.0 {
+            return Color::blank();
+        }
+        let cos_refracted = f64::sqrt(1.0 - (1.0 - cos_theta * cos_theta) / (self.ior * self.ior));
+        let path = 0.5 * self.thickness / cos_refracted;
+        let absorbed = |t: f64| f64::powf(f64::clamp(t, 1e-6, 1.0), path);
+        &Color::from(
+            absorbed(self.tint.x()),
+            absorbed(self.tint.y()),
+            absorbed(self.tint.z()),
+        ) * (1.0 - fresnel_dielectric(cos_theta, self.ior))
+    }
+
+    fn transmittance(&self, wo: &Vec3, wi: &Vec3) -> Color {
+        &self.one_way(wo.z()) * &self.one_way(wi.z())
+    }
+
+    fn scatter_coat(&self, r_in: &Ray, rec: &HitRecord, uvw: Onb, wo: Vec3) -> ScatterRecord {
+        // A reflection off the coat alone, weighted by the coat's BRDF over its pdf.
+        let mut srec = ScatterRecord::blank();
+        srec.skip_pdf = true;
+        if self.distribution.is_smooth() {
+            let f = fresnel_dielectric(wo.z(), self.ior);
+            let direction = reflect(&Vec3::unit_vector(r_in.direction()), &rec.normal);
+            srec.attenuation = Color::from(f, f, f);
+            srec.skip_pdf_ray = Ray::from_set_time(rec.p, direction, r_in.time());
+            return srec;
+        }
+        let pdf = GgxPdf::from(uvw.clone(), wo, self.distribution.clone());
+        let direction = pdf.generate();
+        let pdf_val = pdf.value(&direction);
+        let wi = uvw.vec3_to_local(&Vec3::unit_vector(&direction));
+        let f = if pdf_val > 0.0 {
+            dielectric_reflection(&self.distribution, &wo, &wi, self.ior) / pdf_val
+        } else {
+            0.0
+        };
+        srec.attenuation = Color::from(f, f, f);
+        srec.skip_pdf_ray = Ray::from_set_time(rec.p, direction, r_in.time());
+        srec
+    }
+}
+
+impl Material for Coated {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        let (uvw, wo) = self.frame(r_in, rec);
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        // The coat is sampled about as often as it reflects.
+        let p_coat = f64::clamp(fresnel_dielectric(wo.z(), self.ior), 0.05, 0.95);
+
+        let mut base = match self.base.scatter(r_in, rec) {
+            None => return Some(self.scatter_coat(r_in, rec, uvw, wo)),
+            Some(x) => x,
+        };
+
+        if !base.skip_pdf && !self.distribution.is_smooth() {
+            // Both layers can be evaluated in any direction, so their pdfs are mixed and
+            // `scattering_color` adds the layers up.
+            let coat = GgxPdf::from(uvw, wo, self.distribution.clone());
+            base.pdf_ptr = Box::new(MixturePdf::weighted(vec![
+                (p_coat, Box::new(coat)),
+                (1.0 - p_coat, base.pdf_ptr),
+            ]));
+            return Some(base);
+        }
+
+        // Otherwise one of the layers is a perfect mirror, so one of them is picked at random
+        // and the chance of picking it divided out.
+        if random_double() < p_coat {
+            let mut srec = self.scatter_coat(r_in, rec, uvw, wo);
+            srec.attenuation = &srec.attenuation / p_coat;
+            return Some(srec);
+        }
+        base.attenuation = &base.attenuation / (1.0 - p_coat);
+        if base.skip_pdf {
+            let wi = uvw.vec3_to_local(&Vec3::unit_vector(base.skip_pdf_ray.direction()));
+            base.attenuation = &base.attenuation * &self.transmittance(&wo, &wi);
+        }
+        Some(base)
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord, u: f64, v: f64, p: &Point3) -> Color {
+        // Light from the base only has to get out through the coat.
+        let (_, wo) = self.frame(r_in, rec);
+        &self.base.emitted(r_in, rec, u, v, p) * &self.one_way(wo.z())
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // The luminance of `scattering_color` over a white base, for callers that need a
+        // single number.
+        let white = Color::from(1.0, 1.0, 1.0);
+        luminance(&self.scattering_color(r_in, rec, &white, scattered))
+    }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        let (uvw, wo) = self.frame(r_in, rec);
+        let wi = uvw.vec3_to_local(&Vec3::unit_vector(scattered.direction()));
+        let base = self
+            .base
+            .scattering_color(r_in, rec, attenuation, scattered);
+        let under = &base * &self.transmittance(&wo, &wi);
+        if self.distribution.is_smooth() {
+            // The coat's mirror reflection can't be hit by chance; `scatter` samples it.
+            return under;
+        }
+        let coat = dielectric_reflection(&self.distribution, &wo, &wi, self.ior);
+        &under + &Color::from(coat, coat, coat)
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.base.is_emissive()
+    }
+
+    fn emission_estimate(&self) -> Color {
+        self.base.emission_estimate()
+    }
+
+    fn emission_beam(&self) -> Option<(Vec3, f64)> {
+        self.base.emission_beam()
+    }
+}
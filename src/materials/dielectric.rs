@@ -1,49 +1,301 @@
 use crate::hittables::hittable::HitRecord;
 use crate::materials::material::{Material, ScatterRecord};
+use crate::materials::microfacet::{
+    dielectric_reflection, dielectric_transmission, fresnel_dielectric, refraction_half_vector,
+    TrowbridgeReitz,
+};
+use crate::materials::thin_film::ThinFilm;
 use crate::math_structures::color::Color;
+use crate::math_structures::onb::Onb;
 use crate::math_structures::ray::Ray;
 use crate::math_structures::vec3::{reflect, Vec3};
+use crate::pdf::henyey_greenstein_pdf::HenyeyGreensteinPdf;
+use crate::pdf::rough_dielectric_pdf::RoughDielectricPdf;
 use crate::rtweekend::random_double;
+use crate::volume::constant_medium::sample_distance;
+
+// The inside of a dielectric as seen by a path passing through it. Paths keep a list of the
+// media they are in, so overlapping volumes such as a liquid filling a glass can be resolved:
+// where volumes overlap, the one with the highest priority is the one light travels through.
+#[derive(Clone)]
+pub struct Medium {
+    pub(crate) priority: i64,
+    pub(crate) ir: f64,
+    pub(crate) absorption: Color,
+    pub(crate) scattering: Color,
+    pub(crate) anisotropy: f64,
+}
+
+impl Medium {
+    pub fn same_as(&self, other: &Medium) -> bool {
+        self.priority == other.priority
+            && self.ir == other.ir
+            && self.absorption.e == other.absorption.e
+            && self.scattering.e == other.scattering.e
+            && self.anisotropy == other.anisotropy
+    }
+
+    pub fn transmittance(&self, distance: f64) -> Color {
+        // Beer-Lambert law: the fraction of light left after `distance` inside the medium
+        // without being absorbed or scattered away.
+        let extinction = &self.absorption + &self.scattering;
+        Color::from(
+            f64::exp(-extinction.x() * distance),
+            f64::exp(-extinction.y() * distance),
+            f64::exp(-extinction.z() * distance),
+        )
+    }
+
+    pub fn sample_scattering(&self, distance: f64, throughput: &Color) -> (Option<f64>, Color) {
+        // Follows light for up to `distance` through the medium. Returns how far it got before
+        // hitting a particle, if it did, and what to multiply the path's throughput by. The
+        // distance is drawn for one color channel, picked in proportion to the throughput so
+        // that long random walks don't favour one color, with the weight making up for the
+        // others.
+        if self.scattering.length_squared() <= 0.0 {
+            return (None, self.transmittance(distance));
+        }
+        let total = throughput.x() + throughput.y() + throughput.z();
+        if total <= 0.0 {
+            return (None, Color::blank());
+        }
+        let probability = throughput / total;
+        let extinction = &self.absorption + &self.scattering;
+        let mut u = random_double();
+        let mut channel = 2;
+        for c in 0..2 {
+            if u < probability[c] {
+                channel = c;
+                break;
+            }
+            u -= probability[c];
+        }
+
+        let t = sample_distance(extinction[channel]);
+        if t < distance {
+            let tr = self.transmittance(t);
+            let pdf = (0..3)
+                .map(|c| probability[c] * extinction[c] * tr[c])
+                .sum::<f64>();
+            return (Some(t), &(&self.scattering * &tr) / pdf);
+        }
+        let tr = self.transmittance(distance);
+        let p = (0..3).map(|c| probability[c] * tr[c]).sum::<f64>();
+        if p <= 0.0 {
+            return (None, Color::blank());
+        }
+        (None, &tr / p)
+    }
+
+    pub fn phase(&self, direction: &Vec3) -> HenyeyGreensteinPdf {
+        // Directions light scatters into off a particle, for light travelling along `direction`.
+        HenyeyGreensteinPdf::from(direction, self.anisotropy)
+    }
+}
+
+pub fn current_medium(media: &[Medium]) -> Option<&Medium> {
+    // The medium light travels through: the highest priority one, the latest entered if tied.
+    media.iter().max_by_key(|m| m.priority)
+}
 
 #[derive(Clone)]
 pub struct Dielectric {
     ir: f64,
+    distribution: TrowbridgeReitz,
+    priority: i64,
+    absorption: Color,
+    scattering: Color,
+    anisotropy: f64,
+    film: Option<ThinFilm>,
 }
 
 impl Dielectric {
     pub fn from(index_of_refraction: f64) -> Dielectric {
+        Dielectric::rough(index_of_refraction, 0.0)
+    }
+    pub fn rough(index_of_refraction: f64, roughness: f64) -> Dielectric {
+        // Frosted glass: the surface is made of tiny smooth facets with GGX distributed
+        // normals, each reflecting and refracting like clear glass.
         Dielectric {
             ir: index_of_refraction,
+            distribution: TrowbridgeReitz::from(roughness, 0.0),
+            priority: 0,
+            absorption: Color::blank(),
+            scattering: Color::blank(),
+            anisotropy: 0.0,
+            film: None,
         }
     }
-}
+    pub fn with_roughness(mut self, roughness: f64) -> Dielectric {
+        self.distribution = TrowbridgeReitz::from(roughness, 0.0);
+        self
+    }
+    pub fn with_absorption(mut self, absorption: Color) -> Dielectric {
+        // Absorption coefficients for red, green and blue, per unit distance travelled inside.
+        self.absorption = absorption;
+        self
+    }
+    pub fn with_color(self, color: Color, distance: f64) -> Dielectric {
+        // Tinted so that white light comes out as `color` after `distance` inside.
+        let coefficient = |c: f64| -f64::ln(f64::clamp(c, 1e-6, 1.0)) / distance;
+        self.with_absorption(Color::from(
+            coefficient(color.x()),
+            coefficient(color.y()),
+            coefficient(color.z()),
+        ))
+    }
+    pub fn with_scattering(mut self, scattering: Color) -> Dielectric {
+        // Scattering coefficients for red, green and blue, per unit distance travelled inside.
+        // Light entering the surface takes a random walk between particles before it comes
+        // back out, as in skin, wax, marble or milk.
+        self.scattering = scattering;
+        self
+    }
+    pub fn with_anisotropy(mut self, anisotropy: f64) -> Dielectric {
+        // The Henyey-Greenstein g of the particles inside, from -1 for scattering backwards
+        // to 1 for forwards.
+        self.anisotropy = anisotropy;
+        self
+    }
+
+    // Measured materials from Jensen et al., "A Practical Model for Subsurface Light
+    // Transport", with coefficients per millimetre. `mm_per_unit` is how many millimetres one
+    // unit of the scene is.
+    pub fn marble(mm_per_unit: f64) -> Dielectric {
+        Dielectric::from(1.5)
+            .with_scattering(&Color::from(2.19, 2.62, 3.00) * mm_per_unit)
+            .with_absorption(&Color::from(0.0021, 0.0041, 0.0071) * mm_per_unit)
+    }
+    pub fn skin(mm_per_unit: f64) -> Dielectric {
+        Dielectric::from(1.3)
+            .with_scattering(&Color::from(0.74, 0.88, 1.01) * mm_per_unit)
+            .with_absorption(&Color::from(0.032, 0.17, 0.48) * mm_per_unit)
+    }
+    pub fn whole_milk(mm_per_unit: f64) -> Dielectric {
+        Dielectric::from(1.3)
+            .with_scattering(&Color::from(2.55, 3.21, 3.77) * mm_per_unit)
+            .with_absorption(&Color::from(0.0011, 0.0024, 0.014) * mm_per_unit)
+    }
+    pub fn ketchup(mm_per_unit: f64) -> Dielectric {
+        Dielectric::from(1.3)
+            .with_scattering(&Color::from(0.18, 0.07, 0.03) * mm_per_unit)
+            .with_absorption(&Color::from(0.061, 0.97, 1.45) * mm_per_unit)
+    }
+
+    pub fn with_thin_film(mut self, film: ThinFilm) -> Dielectric {
+        // A clear film over the surface. A soap bubble is a film of water on a dielectric with
+        // an index of refraction of 1.
+        self.film = Some(film);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i64) -> Dielectric {
+        // Where this overlaps another dielectric, the higher priority one fills the overlap.
+        // Give a liquid a higher priority than its glass, and ice a higher one than the drink.
+        self.priority = priority;
+        self
+    }
 
-pub fn reflectance(cosine: f64, ref_idx: f64) -> f64 {
-    // Use Schlick's approximation for reflectance.
-    let mut r0 = (1.0 - ref_idx) / (1.0 + ref_idx);
-    r0 = r0 * r0;
-    r0 + (1.0 - r0) * (1.0 - cosine).powf(5.0)
+    pub fn medium(&self) -> Medium {
+        Medium {
+            priority: self.priority,
+            ir: self.ir,
+            absorption: self.absorption,
+            scattering: self.scattering,
+            anisotropy: self.anisotropy,
+        }
+    }
+
+    pub fn surrounded_by(&self, outside_ir: f64) -> Dielectric {
+        // The same surface with another medium than air outside it, so it bends light by the
+        // ratio of the two indices of refraction.
+        let mut d = self.clone();
+        d.ir = self.ir / outside_ir;
+        d
+    }
+
+    fn eta(&self, rec: &HitRecord) -> f64 {
+        // Index of refraction on the far side of the surface over the near side.
+        if rec.front_face {
+            self.ir
+        } else {
+            1.0 / self.ir
+        }
+    }
+
+    fn film_reflectance(&self, film: &ThinFilm, rec: &HitRecord, cos_theta: f64) -> Color {
+        // The film is on the outside, so light from inside passes through the dielectric first.
+        let (outside, inside) = if rec.front_face {
+            (1.0, self.ir)
+        } else {
+            (self.ir, 1.0)
+        };
+        film.reflectance(
+            rec,
+            cos_theta,
+            outside,
+            &Color::from(inside, inside, inside),
+            &Color::blank(),
+        )
+    }
+
+    fn local_directions(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> (Vec3, Vec3) {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        (
+            uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction())),
+            uvw.vec3_to_local(&Vec3::unit_vector(scattered.direction())),
+        )
+    }
 }
 
 impl Material for Dielectric {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
         let mut srec = ScatterRecord::blank();
         srec.attenuation = Color::from(1.0, 1.0, 1.0);
+        let eta = self.eta(rec);
+        let unit_direction = Vec3::unit_vector(r_in.direction());
+
+        if !self.distribution.is_smooth() {
+            let mut uvw = Onb::blank();
+            uvw.build_from_w(&rec.normal);
+            let wo = uvw.vec3_to_local(&-&unit_direction);
+            srec.pdf_ptr = Box::new(RoughDielectricPdf::from(
+                uvw,
+                wo,
+                eta,
+                self.distribution.clone(),
+            ));
+            srec.skip_pdf = false;
+            return Some(srec);
+        }
+
         srec.skip_pdf = true;
-        let refraction_ratio = if rec.front_face {
-            1.0 / self.ir
-        } else {
-            self.ir
-        };
+        let refraction_ratio = 1.0 / eta;
 
-        let unit_direction = Vec3::unit_vector(r_in.direction());
         let cos_theta = f64::min(Vec3::dot(&-&unit_direction, &rec.normal), 1.0);
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = refraction_ratio * sin_theta > 1.0;
         let direction;
 
-        if cannot_refract || reflectance(cos_theta, refraction_ratio) > random_double() {
+        if let (false, Some(film)) = (cannot_refract, &self.film) {
+            // The film reflects each color differently, so the path follows their average and
+            // is weighted by how far each color is from it.
+            let reflectance = self.film_reflectance(film, rec, cos_theta);
+            let p = f64::clamp(
+                (reflectance.x() + reflectance.y() + reflectance.z()) / 3.0,
+                1e-3,
+                1.0 - 1e-3,
+            );
+            if p > random_double() {
+                direction = reflect(&unit_direction, &rec.normal);
+                srec.attenuation = &reflectance / p;
+            } else {
+                direction = Vec3::refract(&unit_direction, &rec.normal, refraction_ratio);
+                srec.attenuation = &(&Color::from(1.0, 1.0, 1.0) - &reflectance) / (1.0 - p);
+            }
+        } else if cannot_refract || fresnel_dielectric(cos_theta, eta) > random_double() {
             direction = reflect(&unit_direction, &rec.normal);
         } else {
             direction = Vec3::refract(&unit_direction, &rec.normal, refraction_ratio);
@@ -52,4 +304,54 @@ impl Material for Dielectric {
         srec.skip_pdf_ray = Ray::from_set_time(rec.p, direction, r_in.time());
         Some(srec)
     }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // The microfacet BSDF times the cosine, after Walter et al., "Microfacet Models for
+        // Refraction through Rough Surfaces".
+        let eta = self.eta(rec);
+        let (wo, wi) = self.local_directions(r_in, rec, scattered);
+        if wi.z() > 0.0 {
+            dielectric_reflection(&self.distribution, &wo, &wi, eta)
+        } else {
+            dielectric_transmission(&self.distribution, &wo, &wi, eta)
+        }
+    }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        let film = match &self.film {
+            None => return attenuation * self.scattering_pdf(r_in, rec, scattered),
+            Some(x) => x,
+        };
+        // The film's reflectance takes the place of the plain Fresnel term of the microfacet.
+        let eta = self.eta(rec);
+        let (wo, wi) = self.local_directions(r_in, rec, scattered);
+        let d = &self.distribution;
+        if wi.z() > 0.0 {
+            let wm = &wo + &wi;
+            if wo.z() <= 0.0 || wm.length_squared() <= 0.0 {
+                return Color::blank();
+            }
+            let wm = Vec3::unit_vector(&wm);
+            let reflectance = self.film_reflectance(film, rec, Vec3::dot(&wo, &wm));
+            let f = d.d(&wm) * d.g(&wo, &wi) / (4.0 * wo.z());
+            return &(attenuation * &reflectance) * f;
+        }
+        let wm = match refraction_half_vector(&wo, &wi, eta) {
+            None => return Color::blank(),
+            Some(x) => x,
+        };
+        let cos_o = Vec3::dot(&wo, &wm);
+        let plain = 1.0 - fresnel_dielectric(cos_o, eta);
+        if plain <= 0.0 {
+            return Color::blank();
+        }
+        let transmittance = &Color::from(1.0, 1.0, 1.0) - &self.film_reflectance(film, rec, cos_o);
+        &(attenuation * &transmittance) * (dielectric_transmission(d, &wo, &wi, eta) / plain)
+    }
 }
@@ -1,36 +1,76 @@
 use crate::hittables::hittable::HitRecord;
+use crate::lights::emission_profile::EmissionProfile;
 use crate::materials::material::{Material, ScatterRecord};
-use crate::math_structures::color::Color;
+use crate::math_structures::color::{blackbody, Color};
 use crate::math_structures::ray::Ray;
-use crate::math_structures::vec3::Point3;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::PI;
 use crate::textures::solid_color::SolidColor;
-use crate::textures::texture::Texture;
+use crate::textures::texture::{average, Texture};
 use crate::textures::TexEnum;
 
 #[derive(Clone)]
 pub struct DiffuseLight {
     emit: TexEnum,
+    profile: EmissionProfile,
+    // The profile's average, cached for estimating the light's power.
+    profile_average: Color,
 }
 
 impl DiffuseLight {
     pub fn from_texture(a: TexEnum) -> DiffuseLight {
-        DiffuseLight { emit: a }
+        DiffuseLight {
+            emit: a,
+            profile: EmissionProfile::Uniform,
+            profile_average: Color::from(1.0, 1.0, 1.0),
+        }
     }
     pub fn from_color(c: Color) -> DiffuseLight {
         DiffuseLight {
             emit: TexEnum::SolidColor(SolidColor::from(c)),
+            profile: EmissionProfile::Uniform,
+            profile_average: Color::from(1.0, 1.0, 1.0),
         }
     }
+    pub fn from_kelvin(kelvin: f64, luminance: f64) -> DiffuseLight {
+        // Emits the color of a blackbody at the given temperature with the given luminance,
+        // the brightness of the surface seen from any angle.
+        DiffuseLight::from_color(&blackbody(kelvin) * luminance)
+    }
+    pub fn from_kelvin_power(kelvin: f64, power: f64, area: f64) -> DiffuseLight {
+        // As `from_kelvin`, but bright enough for an emitter of the given area to give off the
+        // given total power (in luminance times area, like `Hittable::power`) from its front.
+        DiffuseLight::from_kelvin(kelvin, power / (PI * area))
+    }
+    pub fn with_profile(mut self, profile: EmissionProfile) -> DiffuseLight {
+        // Shapes the emission by direction, e.g. into a beam. The emitted color becomes the
+        // brightness along the profile's brightest direction; for IES profiles, the fixture's
+        // peak intensity divided by the emitter's area.
+        self.profile_average = profile.average();
+        self.profile = profile;
+        self
+    }
 }
 
 impl Material for DiffuseLight {
     fn scatter(&self, _r_in: &Ray, _rec: &HitRecord) -> Option<ScatterRecord> {
         None
     }
-    fn emitted(&self, _r_in: &Ray, rec: &HitRecord, u: f64, v: f64, p: &Point3) -> Color {
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord, u: f64, v: f64, p: &Point3) -> Color {
         if !rec.front_face {
             return Color::blank();
         }
-        return self.emit.value(u, v, p);
+        let emit = &self.emit.value(u, v, p)
+            * &self.profile.surface_value(&-r_in.direction(), &rec.normal);
+        return rec.tint(emit);
+    }
+    fn is_emissive(&self) -> bool {
+        true
+    }
+    fn emission_estimate(&self) -> Color {
+        &average(&self.emit) * &self.profile_average
+    }
+    fn emission_beam(&self) -> Option<(Vec3, f64)> {
+        self.profile.beam()
     }
 }
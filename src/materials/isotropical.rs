@@ -28,7 +28,7 @@ impl Isotropic {
 impl Material for Isotropic {
     fn scatter(&self, _r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
         let mut srec = ScatterRecord::blank();
-        srec.attenuation = self.albedo.value(rec.u, rec.v, &rec.p);
+        srec.attenuation = rec.tint(self.albedo.value(rec.u, rec.v, &rec.p));
         srec.pdf_ptr = Box::new(SpherePdf::blank());
         srec.skip_pdf = false;
         Some(srec)
@@ -33,6 +33,39 @@ pub trait Material: Clone {
     }
 
     fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
+        // The BSDF times the cosine for light arriving along `scattered`, leaving out the
+        // material's own color. For a Lambertian surface it is also the density `scatter`
+        // samples directions with.
         0.0
     }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        // The BSDF times the cosine for light arriving along `scattered`. Materials whose color
+        // changes with direction override this; the rest scale the attenuation from `scatter` by
+        // the pdf.
+        attenuation * self.scattering_pdf(r_in, rec, scattered)
+    }
+
+    fn is_emissive(&self) -> bool {
+        // Whether objects with this material should be sampled as lights.
+        false
+    }
+
+    fn emission_estimate(&self) -> Color {
+        // Typical emitted radiance, averaged over the directions light leaves in, used to weigh
+        // lights against each other.
+        Color::from(0.0, 0.0, 0.0)
+    }
+
+    fn emission_beam(&self) -> Option<(Vec3, f64)> {
+        // The axis and half angle of a cone holding every direction the material emits in,
+        // for emitters shaped into a beam.
+        None
+    }
 }
@@ -0,0 +1,225 @@
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::Vec3;
+use crate::rtweekend::PI;
+
+// Trowbridge-Reitz (GGX) distribution of microfacet normals. Directions are in a local frame
+// with the surface normal along z. The roughness can be stretched differently along x and y
+// for anisotropic surfaces such as brushed metal.
+#[derive(Clone)]
+pub struct TrowbridgeReitz {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl TrowbridgeReitz {
+    pub fn from(roughness: f64, anisotropy: f64) -> TrowbridgeReitz {
+        // Roughness in [0, 1] is squared into alpha so it looks roughly linear. Anisotropy in
+        // [0, 1] stretches highlights along y, using Disney's mapping.
+        let alpha = f64::powi(f64::clamp(roughness, 0.0, 1.0), 2);
+        let aspect = f64::sqrt(1.0 - 0.9 * f64::clamp(anisotropy, 0.0, 1.0));
+        TrowbridgeReitz {
+            alpha_x: f64::max(alpha / aspect, 1e-4),
+            alpha_y: f64::max(alpha * aspect, 1e-4),
+        }
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        // Too sharp to sample or evaluate reliably; treated as a perfect mirror instead.
+        f64::max(self.alpha_x, self.alpha_y) < 1e-3
+    }
+
+    pub fn d(&self, wm: &Vec3) -> f64 {
+        // Density of microfacets facing wm, per unit projected area.
+        if wm.z() <= 0.0 {
+            return 0.0;
+        }
+        let e = f64::powi(wm.x() / self.alpha_x, 2)
+            + f64::powi(wm.y() / self.alpha_y, 2)
+            + wm.z() * wm.z();
+        1.0 / (PI * self.alpha_x * self.alpha_y * e * e)
+    }
+
+    pub fn lambda(&self, w: &Vec3) -> f64 {
+        let cos2 = w.z() * w.z();
+        if cos2 <= 0.0 {
+            return f64::INFINITY;
+        }
+        let alpha2_tan2 =
+            (f64::powi(self.alpha_x * w.x(), 2) + f64::powi(self.alpha_y * w.y(), 2)) / cos2;
+        (f64::sqrt(1.0 + alpha2_tan2) - 1.0) / 2.0
+    }
+
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        // Fraction of microfacets seen from w that are not hidden by others.
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        // Fraction of microfacets visible from both directions, with their heights correlated.
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    pub fn sample_wm(&self, w: &Vec3, u1: f64, u2: f64) -> Vec3 {
+        // Samples a microfacet normal as seen from w, following Heitz, "Sampling the GGX
+        // Distribution of Visible Normals": the view is stretched to a unit roughness, a point
+        // on the projected hemisphere is picked and the normal under it is unstretched.
+        let wh = Vec3::unit_vector(&Vec3::from(
+            self.alpha_x * w.x(),
+            self.alpha_y * w.y(),
+            f64::abs(w.z()),
+        ));
+        let len_sq = wh.x() * wh.x() + wh.y() * wh.y();
+        let t1 = if len_sq > 0.0 {
+            &Vec3::from(-wh.y(), wh.x(), 0.0) / f64::sqrt(len_sq)
+        } else {
+            Vec3::from(1.0, 0.0, 0.0)
+        };
+        let t2 = Vec3::cross(&wh, &t1);
+
+        let r = f64::sqrt(u1);
+        let phi = 2.0 * PI * u2;
+        let p1 = r * f64::cos(phi);
+        let s = 0.5 * (1.0 + wh.z());
+        let p2 = (1.0 - s) * f64::sqrt(1.0 - p1 * p1) + s * r * f64::sin(phi);
+        let pz = f64::sqrt(f64::max(0.0, 1.0 - p1 * p1 - p2 * p2));
+        let nh = &(&(p1 * &t1) + &(p2 * &t2)) + &(pz * &wh);
+
+        Vec3::unit_vector(&Vec3::from(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            f64::max(1e-6, nh.z()),
+        ))
+    }
+
+    pub fn pdf_wm(&self, w: &Vec3, wm: &Vec3) -> f64 {
+        // Density of `sample_wm` returning wm.
+        if w.z() == 0.0 {
+            return 0.0;
+        }
+        self.g1(w) / f64::abs(w.z()) * self.d(wm) * f64::abs(Vec3::dot(w, wm))
+    }
+}
+
+pub fn charlie_d(alpha: f64, wm: &Vec3) -> f64 {
+    // The Charlie sheen distribution of Estevez and Kulla, "Production Friendly Microfacet
+    // Sheen BRDF": density of microfacets facing wm, which lean away from the normal like the
+    // fibres of velvet, the more so the smaller alpha is.
+    if wm.z() <= 0.0 {
+        return 0.0;
+    }
+    let sin2 = f64::max(0.0, 1.0 - wm.z() * wm.z());
+    (2.0 + 1.0 / alpha) / (2.0 * PI) * f64::powf(sin2, 0.5 / alpha)
+}
+
+pub fn fresnel_conductor(cos_theta: f64, eta: &Color, k: &Color) -> Color {
+    // Unpolarized reflectance of a metal with complex index of refraction eta + ik, for light
+    // arriving at cos_theta to the surface normal from air.
+    let c = f64::clamp(cos_theta, 0.0, 1.0);
+    let reflectance = |eta: f64, k: f64| {
+        let cos2 = c * c;
+        let sin2 = 1.0 - cos2;
+        let t0 = eta * eta - k * k - sin2;
+        let a2b2 = f64::sqrt(t0 * t0 + 4.0 * eta * eta * k * k);
+        let a = f64::sqrt(f64::max(0.0, 0.5 * (a2b2 + t0)));
+        let rs = (a2b2 + cos2 - 2.0 * a * c) / (a2b2 + cos2 + 2.0 * a * c);
+        let t1 = cos2 * a2b2 + sin2 * sin2;
+        let t2 = 2.0 * a * c * sin2;
+        let rp = rs * (t1 - t2) / (t1 + t2);
+        0.5 * (rs + rp)
+    };
+    Color::from(
+        reflectance(eta.x(), k.x()),
+        reflectance(eta.y(), k.y()),
+        reflectance(eta.z(), k.z()),
+    )
+}
+
+pub fn fresnel_schlick(cos_theta: f64, f0: &Color) -> Color {
+    // Schlick's approximation, rising from f0 head on to white at grazing angles.
+    let m = f64::powi(1.0 - f64::clamp(cos_theta, 0.0, 1.0), 5);
+    f0 + &(&(&Color::from(1.0, 1.0, 1.0) - f0) * m)
+}
+
+pub fn fresnel_dielectric(cos_theta: f64, eta: f64) -> f64 {
+    // Exact unpolarized reflectance of a boundary between dielectrics, where eta is the index
+    // of refraction on the far side of the normal over the index on the near side. Light
+    // arriving from behind the normal (negative cos_theta) sees the inverse ratio.
+    let (cos_i, eta) = if cos_theta < 0.0 {
+        (f64::min(-cos_theta, 1.0), 1.0 / eta)
+    } else {
+        (f64::min(cos_theta, 1.0), eta)
+    };
+    let sin2_t = (1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        // Total internal reflection.
+        return 1.0;
+    }
+    let cos_t = f64::sqrt(1.0 - sin2_t);
+    let r_parallel = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let r_perpendicular = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    (r_parallel * r_parallel + r_perpendicular * r_perpendicular) / 2.0
+}
+
+pub fn refract(w: &Vec3, n: &Vec3, eta: f64) -> Option<Vec3> {
+    // Refracts w, pointing away from the surface on the side of n, into the other side. Eta is
+    // as for `fresnel_dielectric`. None under total internal reflection.
+    let cos_i = Vec3::dot(w, n);
+    let sin2_t = f64::max(0.0, 1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return None;
+    }
+    let cos_t = f64::sqrt(1.0 - sin2_t);
+    Some(&(&-w / eta) + &((cos_i / eta - cos_t) * n))
+}
+
+pub fn refraction_half_vector(wo: &Vec3, wi: &Vec3, eta: f64) -> Option<Vec3> {
+    // The microfacet normal that refracts wo into wi, facing wo, or None if no microfacet
+    // facing wo refracts between them.
+    let wm = &(eta * wi) + wo;
+    if wm.length_squared() <= 0.0 {
+        return None;
+    }
+    let wm = Vec3::unit_vector(&wm);
+    let wm = if Vec3::dot(&wm, wo) < 0.0 { -&wm } else { wm };
+    if Vec3::dot(&wm, wi) >= 0.0 {
+        return None;
+    }
+    Some(wm)
+}
+
+pub fn dielectric_reflection(d: &TrowbridgeReitz, wo: &Vec3, wi: &Vec3, eta: f64) -> f64 {
+    // The BSDF times the cosine for light reflecting off a rough dielectric boundary, with
+    // both directions in the local frame on the side of the normal. Eta is as for
+    // `fresnel_dielectric`.
+    if wo.z() <= 0.0 || wi.z() <= 0.0 {
+        return 0.0;
+    }
+    let wm = wo + wi;
+    if wm.length_squared() <= 0.0 {
+        return 0.0;
+    }
+    let wm = Vec3::unit_vector(&wm);
+    let fresnel = fresnel_dielectric(Vec3::dot(wo, &wm), eta);
+    d.d(&wm) * d.g(wo, wi) * fresnel / (4.0 * wo.z())
+}
+
+pub fn dielectric_transmission(d: &TrowbridgeReitz, wo: &Vec3, wi: &Vec3, eta: f64) -> f64 {
+    // As `dielectric_reflection`, for light refracting through the boundary from below.
+    if wo.z() <= 0.0 || wi.z() >= 0.0 {
+        return 0.0;
+    }
+    let wm = match refraction_half_vector(wo, wi, eta) {
+        None => return 0.0,
+        Some(x) => x,
+    };
+    let cos_o = Vec3::dot(wo, &wm);
+    let cos_i = Vec3::dot(wi, &wm);
+    let denom = f64::powi(cos_i + cos_o / eta, 2);
+    if denom <= 0.0 {
+        return 0.0;
+    }
+    let transmittance = 1.0 - fresnel_dielectric(cos_o, eta);
+    // Radiance is squeezed into a narrower cone when it enters a denser medium, so it is
+    // scaled by the squared ratio of the indices on the way through.
+    d.d(&wm) * d.g(wo, wi) * transmittance * f64::abs(cos_i * cos_o) / (wo.z() * denom * eta * eta)
+}
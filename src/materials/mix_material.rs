@@ -0,0 +1,107 @@
+use crate::hittables::hittable::HitRecord;
+use crate::materials::material::{Material, ScatterRecord};
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::Point3;
+use crate::textures::texture::{average, Texture};
+use crate::textures::TexEnum;
+
+// Two materials blended by a texture, such as patches of rust on a metal or a checkerboard of
+// two stones. The weight is read from the texture's red channel: 0 is all the first material
+// and 1 all the second. Each time light scatters, one of them is picked at random by the
+// weight, so either can be a mirror, glass or a mix itself.
+#[derive(Clone)]
+pub struct MixMaterial {
+    first: Box<MatEnum>,
+    second: Box<MatEnum>,
+    weight: Box<TexEnum>,
+}
+
+impl MixMaterial {
+    pub fn from(first: MatEnum, second: MatEnum, weight: TexEnum) -> MixMaterial {
+        MixMaterial {
+            first: Box::new(first),
+            second: Box::new(second),
+            weight: Box::new(weight),
+        }
+    }
+
+    fn weight(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        f64::clamp(self.weight.value(u, v, p).x(), 0.0, 1.0)
+    }
+
+    pub(crate) fn choose(&self, r_in: &Ray, rec: &HitRecord) -> &MatEnum {
+        // The random number comes from hashing the hit, so that `scatter` and
+        // `scattering_color` pick the same material for the same path.
+        let d = r_in.direction();
+        let u = hash(&[rec.p.x(), rec.p.y(), rec.p.z(), d.x(), d.y(), d.z()]);
+        self.choose_with(rec, u)
+    }
+
+    fn choose_with(&self, rec: &HitRecord, u: f64) -> &MatEnum {
+        // Whichever part of [0, 1) u falls in is stretched back to [0, 1) for nested mixes.
+        let w = self.weight(rec.u, rec.v, &rec.p);
+        let (chosen, u) = if u < w {
+            (&*self.second, u / w)
+        } else {
+            (&*self.first, (u - w) / (1.0 - w))
+        };
+        match chosen {
+            MatEnum::MixMaterial(m) => m.choose_with(rec, u),
+            _ => chosen,
+        }
+    }
+}
+
+fn hash(values: &[f64]) -> f64 {
+    // A number in [0, 1) that changes unpredictably with every bit of the inputs, after
+    // SplitMix64.
+    let mut h: u64 = 0x9e3779b97f4a7c15;
+    for value in values {
+        h ^= value.to_bits();
+        h = h.wrapping_add(0x9e3779b97f4a7c15);
+        h = (h ^ (h >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94d049bb133111eb);
+        h ^= h >> 31;
+    }
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl Material for MixMaterial {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        self.choose(r_in, rec).scatter(r_in, rec)
+    }
+
+    fn emitted(&self, r_in: &Ray, rec: &HitRecord, u: f64, v: f64, p: &Point3) -> Color {
+        let w = self.weight(u, v, p);
+        &(&self.first.emitted(r_in, rec, u, v, p) * (1.0 - w))
+            + &(&self.second.emitted(r_in, rec, u, v, p) * w)
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // That of the material picked for this path, to agree with `scatter` and
+        // `scattering_color`.
+        self.choose(r_in, rec).scattering_pdf(r_in, rec, scattered)
+    }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        self.choose(r_in, rec)
+            .scattering_color(r_in, rec, attenuation, scattered)
+    }
+
+    fn is_emissive(&self) -> bool {
+        self.first.is_emissive() || self.second.is_emissive()
+    }
+
+    fn emission_estimate(&self) -> Color {
+        let w = f64::clamp(average(&*self.weight).x(), 0.0, 1.0);
+        &(&self.first.emission_estimate() * (1.0 - w)) + &(&self.second.emission_estimate() * w)
+    }
+}
@@ -1,16 +1,23 @@
 use crate::hittables::hittable::HitRecord;
 use crate::math_structures::color::Color;
 use crate::math_structures::ray::Ray;
-use crate::math_structures::vec3::Point3;
+use crate::math_structures::vec3::{Point3, Vec3};
 
 use crate::materials::material::{Material, ScatterRecord};
 
+pub mod cloth;
+pub mod coated;
 pub mod dielectric;
 pub mod diffuse_light;
 pub mod isotropical;
 pub mod lambertian;
 pub mod material;
 pub mod metal;
+pub mod microfacet;
+pub mod mix_material;
+pub mod oren_nayar;
+pub mod principled;
+pub mod thin_film;
 
 #[derive(Clone)]
 pub enum MatEnum {
@@ -20,6 +27,11 @@ pub enum MatEnum {
     Dielectric(dielectric::Dielectric),
     DiffuseLight(diffuse_light::DiffuseLight),
     Isotropic(isotropical::Isotropic),
+    Principled(principled::Principled),
+    Coated(coated::Coated),
+    OrenNayar(oren_nayar::OrenNayar),
+    Cloth(cloth::Cloth),
+    MixMaterial(mix_material::MixMaterial),
 }
 
 #[derive(Clone)]
@@ -47,6 +59,21 @@ impl Material for MatEnum {
             MatEnum::Isotropic(y) => {
                 x = y.scatter(r_in, rec);
             }
+            MatEnum::Principled(y) => {
+                x = y.scatter(r_in, rec);
+            }
+            MatEnum::Coated(y) => {
+                x = y.scatter(r_in, rec);
+            }
+            MatEnum::OrenNayar(y) => {
+                x = y.scatter(r_in, rec);
+            }
+            MatEnum::Cloth(y) => {
+                x = y.scatter(r_in, rec);
+            }
+            MatEnum::MixMaterial(y) => {
+                x = y.scatter(r_in, rec);
+            }
         }
         x
     }
@@ -72,6 +99,21 @@ impl Material for MatEnum {
             MatEnum::Isotropic(y) => {
                 x = y.emitted(r_in, rec, u, v, p);
             }
+            MatEnum::Principled(y) => {
+                x = y.emitted(r_in, rec, u, v, p);
+            }
+            MatEnum::Coated(y) => {
+                x = y.emitted(r_in, rec, u, v, p);
+            }
+            MatEnum::OrenNayar(y) => {
+                x = y.emitted(r_in, rec, u, v, p);
+            }
+            MatEnum::Cloth(y) => {
+                x = y.emitted(r_in, rec, u, v, p);
+            }
+            MatEnum::MixMaterial(y) => {
+                x = y.emitted(r_in, rec, u, v, p);
+            }
         }
         x
     }
@@ -97,7 +139,68 @@ impl Material for MatEnum {
             MatEnum::Isotropic(y) => {
                 x = y.scattering_pdf(r_in, rec, scattered);
             }
+            MatEnum::Principled(y) => {
+                x = y.scattering_pdf(r_in, rec, scattered);
+            }
+            MatEnum::Coated(y) => {
+                x = y.scattering_pdf(r_in, rec, scattered);
+            }
+            MatEnum::OrenNayar(y) => {
+                x = y.scattering_pdf(r_in, rec, scattered);
+            }
+            MatEnum::Cloth(y) => {
+                x = y.scattering_pdf(r_in, rec, scattered);
+            }
+            MatEnum::MixMaterial(y) => {
+                x = y.scattering_pdf(r_in, rec, scattered);
+            }
         }
         x
     }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        match self {
+            MatEnum::Dielectric(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            MatEnum::Metal(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            MatEnum::Principled(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            MatEnum::Coated(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            MatEnum::Cloth(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            MatEnum::MixMaterial(y) => y.scattering_color(r_in, rec, attenuation, scattered),
+            _ => attenuation * self.scattering_pdf(r_in, rec, scattered),
+        }
+    }
+
+    fn is_emissive(&self) -> bool {
+        match self {
+            MatEnum::DiffuseLight(y) => y.is_emissive(),
+            MatEnum::Principled(y) => y.is_emissive(),
+            MatEnum::Coated(y) => y.is_emissive(),
+            MatEnum::MixMaterial(y) => y.is_emissive(),
+            _ => false,
+        }
+    }
+
+    fn emission_estimate(&self) -> Color {
+        match self {
+            MatEnum::DiffuseLight(y) => y.emission_estimate(),
+            MatEnum::Principled(y) => y.emission_estimate(),
+            MatEnum::Coated(y) => y.emission_estimate(),
+            MatEnum::MixMaterial(y) => y.emission_estimate(),
+            _ => Color::from(0.0, 0.0, 0.0),
+        }
+    }
+
+    fn emission_beam(&self) -> Option<(Vec3, f64)> {
+        match self {
+            MatEnum::DiffuseLight(y) => y.emission_beam(),
+            MatEnum::Coated(y) => y.emission_beam(),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use crate::hittables::hittable::HitRecord;
+use crate::materials::material::{Material, ScatterRecord};
+use crate::math_structures::color::Color;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::cosine_pdf::CosinePdf;
+use crate::rtweekend::PI;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+use crate::textures::TexEnum;
+
+// Rough diffuse surfaces such as clay, plaster or the moon's dust, after Oren and Nayar,
+// "Generalization of Lambert's Reflectance Model". The surface is made of tiny V-shaped
+// Lambertian grooves whose slopes have a standard deviation of sigma radians. Facets facing
+// the light are also facing back at it, so rough surfaces scatter light back towards where it
+// came from and look flatter than Lambertian ones. A sigma of 0 is Lambertian.
+#[derive(Clone)]
+pub struct OrenNayar {
+    albedo: TexEnum,
+    sigma: Box<TexEnum>,
+}
+
+impl OrenNayar {
+    pub fn from_texture(a: TexEnum, sigma: TexEnum) -> OrenNayar {
+        // Sigma is read from the texture's red channel.
+        OrenNayar {
+            albedo: a,
+            sigma: Box::new(sigma),
+        }
+    }
+    pub fn from_color(a: Color, sigma: f64) -> OrenNayar {
+        OrenNayar {
+            albedo: TexEnum::SolidColor(SolidColor::from(a)),
+            sigma: Box::new(TexEnum::SolidColor(SolidColor::from_rgb(
+                sigma, sigma, sigma,
+            ))),
+        }
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(&self, _r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        let mut srec = ScatterRecord::blank();
+        srec.attenuation = rec.tint(self.albedo.value(rec.u, rec.v, &rec.p));
+        srec.pdf_ptr = Box::new(CosinePdf::from(&rec.normal));
+        srec.skip_pdf = false;
+
+        Some(srec)
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.vec3_to_local(&Vec3::unit_vector(scattered.direction()));
+        if wi.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let sigma2 = f64::powi(self.sigma.value(rec.u, rec.v, &rec.p).x(), 2);
+        let a = 1.0 - sigma2 / (2.0 * (sigma2 + 0.33));
+        let b = 0.45 * sigma2 / (sigma2 + 0.09);
+
+        // The backscattering term grows as the two directions line up around the normal and
+        // move away from it.
+        let sin_i = f64::sqrt(f64::max(0.0, 1.0 - wi.z() * wi.z()));
+        let sin_o = f64::sqrt(f64::max(0.0, 1.0 - wo.z() * wo.z()));
+        let mut backscatter = 0.0;
+        if sin_i > 1e-4 && sin_o > 1e-4 && wo.z() > 0.0 {
+            let cos_phi = (wi.x() * wo.x() + wi.y() * wo.y()) / (sin_i * sin_o);
+            let (sin_alpha, tan_beta) = if wi.z() > wo.z() {
+                (sin_o, sin_i / wi.z())
+            } else {
+                (sin_i, sin_o / wo.z())
+            };
+            backscatter = f64::max(0.0, cos_phi) * sin_alpha * tan_beta;
+        }
+        wi.z() / PI * (a + b * backscatter)
+    }
+}
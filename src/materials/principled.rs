@@ -0,0 +1,312 @@
+use crate::hittables::hittable::HitRecord;
+use crate::materials::material::{Material, ScatterRecord};
+use crate::materials::microfacet::{dielectric_transmission, fresnel_schlick, TrowbridgeReitz};
+use crate::math_structures::color::{luminance, Color};
+use crate::math_structures::onb::Onb;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::pdf::cosine_pdf::CosinePdf;
+use crate::pdf::ggx_pdf::GgxPdf;
+use crate::pdf::mixture_pdf::MixturePdf;
+use crate::pdf::pdf::Pdf;
+use crate::pdf::rough_dielectric_pdf::RoughDielectricPdf;
+use crate::rtweekend::PI;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::{average, Texture};
+use crate::textures::TexEnum;
+
+// An artist-friendly uber material after Burley, "Physically Based Shading at Disney". Every
+// parameter is a texture; those that are single numbers in [0, 1] are read from the red
+// channel. A diffuse base, a sheen for cloth, a GGX specular that turns into a metal as
+// `metallic` goes to 1, a clear lacquer coat on top and glass-like transmission are blended
+// into one BSDF, and each lobe is importance sampled.
+#[derive(Clone)]
+pub struct Principled {
+    base_color: Box<TexEnum>,
+    metallic: Box<TexEnum>,
+    roughness: Box<TexEnum>,
+    specular: Box<TexEnum>,
+    specular_tint: Box<TexEnum>,
+    sheen: Box<TexEnum>,
+    sheen_tint: Box<TexEnum>,
+    clearcoat: Box<TexEnum>,
+    clearcoat_roughness: Box<TexEnum>,
+    transmission: Box<TexEnum>,
+    emission: Box<TexEnum>,
+}
+
+// The parameters looked up at one point of the surface.
+struct Parameters {
+    base_color: Color,
+    metallic: f64,
+    roughness: f64,
+    specular: f64,
+    specular_tint: f64,
+    sheen: f64,
+    sheen_tint: f64,
+    clearcoat: f64,
+    clearcoat_roughness: f64,
+    transmission: f64,
+}
+
+impl Principled {
+    pub fn from(base_color: TexEnum) -> Principled {
+        // A plastic-like dielectric with a medium roughness.
+        Principled {
+            base_color: Box::new(base_color),
+            metallic: Box::new(constant(0.0)),
+            roughness: Box::new(constant(0.5)),
+            specular: Box::new(constant(0.5)),
+            specular_tint: Box::new(constant(0.0)),
+            sheen: Box::new(constant(0.0)),
+            sheen_tint: Box::new(constant(0.5)),
+            clearcoat: Box::new(constant(0.0)),
+            clearcoat_roughness: Box::new(constant(0.03)),
+            transmission: Box::new(constant(0.0)),
+            emission: Box::new(constant(0.0)),
+        }
+    }
+    pub fn from_color(base_color: Color) -> Principled {
+        Principled::from(TexEnum::SolidColor(SolidColor::from(base_color)))
+    }
+
+    pub fn with_metallic(mut self, metallic: TexEnum) -> Principled {
+        // 0 for dielectrics, 1 for metals, which reflect in the base color and have no diffuse.
+        self.metallic = Box::new(metallic);
+        self
+    }
+    pub fn with_roughness(mut self, roughness: TexEnum) -> Principled {
+        self.roughness = Box::new(roughness);
+        self
+    }
+    pub fn with_specular(mut self, specular: TexEnum) -> Principled {
+        // Head-on reflectance of dielectrics, where 0.5 is 4% like most plastics and glass.
+        // It also sets the index of refraction for transmission, 1.5 at 0.5.
+        self.specular = Box::new(specular);
+        self
+    }
+    pub fn with_specular_tint(mut self, specular_tint: TexEnum) -> Principled {
+        // How far the dielectric reflection is tinted towards the base color.
+        self.specular_tint = Box::new(specular_tint);
+        self
+    }
+    pub fn with_sheen(mut self, sheen: TexEnum) -> Principled {
+        // A soft glow at grazing angles, as on velvet and other cloth.
+        self.sheen = Box::new(sheen);
+        self
+    }
+    pub fn with_sheen_tint(mut self, sheen_tint: TexEnum) -> Principled {
+        self.sheen_tint = Box::new(sheen_tint);
+        self
+    }
+    pub fn with_clearcoat(mut self, clearcoat: TexEnum) -> Principled {
+        // Strength of a colorless lacquer layer, as on car paint.
+        self.clearcoat = Box::new(clearcoat);
+        self
+    }
+    pub fn with_clearcoat_roughness(mut self, clearcoat_roughness: TexEnum) -> Principled {
+        self.clearcoat_roughness = Box::new(clearcoat_roughness);
+        self
+    }
+    pub fn with_transmission(mut self, transmission: TexEnum) -> Principled {
+        // How much of the dielectric part lets light through like glass tinted by the base
+        // color, instead of scattering it diffusely.
+        self.transmission = Box::new(transmission);
+        self
+    }
+    pub fn with_emission(mut self, emission: TexEnum) -> Principled {
+        // Light given off by the front of the surface, on top of what it reflects.
+        self.emission = Box::new(emission);
+        self
+    }
+
+    fn parameters(&self, rec: &HitRecord) -> Parameters {
+        let scalar = |t: &TexEnum| f64::clamp(t.value(rec.u, rec.v, &rec.p).x(), 0.0, 1.0);
+        Parameters {
+            base_color: rec.tint(self.base_color.value(rec.u, rec.v, &rec.p)),
+            metallic: scalar(&self.metallic),
+            roughness: scalar(&self.roughness),
+            specular: scalar(&self.specular),
+            specular_tint: scalar(&self.specular_tint),
+            sheen: scalar(&self.sheen),
+            sheen_tint: scalar(&self.sheen_tint),
+            clearcoat: scalar(&self.clearcoat),
+            clearcoat_roughness: scalar(&self.clearcoat_roughness),
+            transmission: scalar(&self.transmission),
+        }
+    }
+
+    fn bsdf(&self, params: &Parameters, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        // The BSDF times the cosine, with both directions in the local frame of the normal.
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.vec3_to_local(&Vec3::unit_vector(scattered.direction()));
+        if wo.z() <= 0.0 {
+            return Color::blank();
+        }
+        let p = params;
+
+        if wi.z() < 0.0 {
+            let weight = (1.0 - p.metallic) * p.transmission;
+            if weight <= 0.0 {
+                return Color::blank();
+            }
+            let distribution = TrowbridgeReitz::from(p.roughness, 0.0);
+            let f = dielectric_transmission(&distribution, &wo, &wi, p.eta(rec));
+            return &p.base_color * (weight * f);
+        }
+
+        let wm = &wo + &wi;
+        if wi.z() == 0.0 || wm.length_squared() <= 0.0 {
+            return Color::blank();
+        }
+        let wm = Vec3::unit_vector(&wm);
+        let cos_d = Vec3::dot(&wi, &wm);
+        let white = Color::from(1.0, 1.0, 1.0);
+
+        // Burley's diffuse, which darkens smooth surfaces and brightens rough ones at grazing
+        // angles, and the sheen, both only on the dielectric part.
+        let fd90 = 0.5 + 2.0 * p.roughness * cos_d * cos_d;
+        let fd = (1.0 + (fd90 - 1.0) * schlick_weight(wi.z()))
+            * (1.0 + (fd90 - 1.0) * schlick_weight(wo.z()));
+        let diffuse = &p.base_color * ((1.0 - p.transmission) * fd / PI);
+        let sheen_color = lerp(&white, &p.tint(), p.sheen_tint);
+        let sheen = &sheen_color * (p.sheen * schlick_weight(cos_d));
+        let dielectric = &(&diffuse + &sheen) * ((1.0 - p.metallic) * wi.z());
+
+        let distribution = TrowbridgeReitz::from(p.roughness, 0.0);
+        let fresnel = fresnel_schlick(cos_d, &p.specular_color());
+        let specular = &fresnel * (distribution.d(&wm) * distribution.g(&wo, &wi) / (4.0 * wo.z()));
+
+        let coat = TrowbridgeReitz::from(p.clearcoat_roughness, 0.0);
+        let coat_fresnel = fresnel_schlick(cos_d, &Color::from(0.04, 0.04, 0.04)).x();
+        let clearcoat =
+            0.25 * p.clearcoat * coat_fresnel * coat.d(&wm) * coat.g(&wo, &wi) / (4.0 * wo.z());
+
+        &(&dielectric + &specular) + &Color::from(clearcoat, clearcoat, clearcoat)
+    }
+}
+
+impl Parameters {
+    fn tint(&self) -> Color {
+        // The base color's hue and saturation at unit luminance.
+        let lum = luminance(&self.base_color);
+        if lum > 0.0 {
+            &self.base_color / lum
+        } else {
+            Color::from(1.0, 1.0, 1.0)
+        }
+    }
+
+    fn specular_color(&self) -> Color {
+        // Head-on reflectance of the specular lobe, that of a dielectric blending into the
+        // base color as the surface becomes a metal.
+        let white = Color::from(1.0, 1.0, 1.0);
+        let dielectric = &lerp(&white, &self.tint(), self.specular_tint) * (0.08 * self.specular);
+        lerp(&dielectric, &self.base_color, self.metallic)
+    }
+
+    fn eta(&self, rec: &HitRecord) -> f64 {
+        // The index of refraction whose head-on reflectance is 0.08 * specular, across the
+        // surface from the side the light arrives on.
+        let r = f64::sqrt(f64::min(0.08 * self.specular, 0.99));
+        let ior = (1.0 + r) / (1.0 - r);
+        if rec.front_face {
+            ior
+        } else {
+            1.0 / ior
+        }
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<ScatterRecord> {
+        let p = self.parameters(rec);
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(&rec.normal);
+        let wo = uvw.vec3_to_local(&-&Vec3::unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        // Lobes are picked roughly in proportion to how much light they reflect. The colors all
+        // come from `scattering_color`.
+        let distribution = TrowbridgeReitz::from(p.roughness, 0.0);
+        let dielectric = 1.0 - p.metallic;
+        let lobes: Vec<(f64, Box<dyn Pdf>)> = vec![
+            (
+                dielectric * ((1.0 - p.transmission) * luminance(&p.base_color) + 0.1 * p.sheen),
+                Box::new(CosinePdf::from(&rec.normal)),
+            ),
+            (
+                f64::max(luminance(&p.specular_color()), 0.1),
+                Box::new(GgxPdf::from(uvw.clone(), wo, distribution.clone())),
+            ),
+            (
+                0.1 * p.clearcoat,
+                Box::new(GgxPdf::from(
+                    uvw.clone(),
+                    wo,
+                    TrowbridgeReitz::from(p.clearcoat_roughness, 0.0),
+                )),
+            ),
+            (
+                dielectric * p.transmission,
+                Box::new(RoughDielectricPdf::from(uvw, wo, p.eta(rec), distribution)),
+            ),
+        ];
+
+        let mut srec = ScatterRecord::blank();
+        srec.attenuation = Color::from(1.0, 1.0, 1.0);
+        srec.pdf_ptr = Box::new(MixturePdf::weighted(lobes));
+        srec.skip_pdf = false;
+        Some(srec)
+    }
+
+    fn emitted(&self, _r_in: &Ray, rec: &HitRecord, u: f64, v: f64, p: &Point3) -> Color {
+        if !rec.front_face {
+            return Color::blank();
+        }
+        rec.tint(self.emission.value(u, v, p))
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // The luminance of the BSDF times the cosine over a white base, for callers that need a
+        // single number.
+        let mut params = self.parameters(rec);
+        params.base_color = Color::from(1.0, 1.0, 1.0);
+        luminance(&self.bsdf(&params, r_in, rec, scattered))
+    }
+
+    fn scattering_color(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &Color,
+        scattered: &Ray,
+    ) -> Color {
+        attenuation * &self.bsdf(&self.parameters(rec), r_in, rec, scattered)
+    }
+
+    fn is_emissive(&self) -> bool {
+        luminance(&self.emission_estimate()) > 0.0
+    }
+
+    fn emission_estimate(&self) -> Color {
+        average(&*self.emission)
+    }
+}
+
+pub fn constant(value: f64) -> TexEnum {
+    // A texture giving the same number everywhere, for the scalar parameters.
+    TexEnum::SolidColor(SolidColor::from_rgb(value, value, value))
+}
+
+fn schlick_weight(cos_theta: f64) -> f64 {
+    f64::powi(1.0 - f64::clamp(cos_theta, 0.0, 1.0), 5)
+}
+
+fn lerp(a: &Color, b: &Color, t: f64) -> Color {
+    &(a * (1.0 - t)) + &(b * t)
+}
@@ -0,0 +1,197 @@
+use crate::hittables::hittable::HitRecord;
+use crate::math_structures::color::Color;
+use crate::rtweekend::PI;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+use crate::textures::TexEnum;
+use std::ops::{Add, Div, Mul, Sub};
+
+// Wavelengths in nanometres that red, green and blue are each averaged over. Thick films
+// fade to white as neighbouring wavelengths in a channel go in and out of phase.
+const WAVELENGTHS: [[f64; 3]; 3] = [
+    [600.0, 640.0, 680.0],
+    [510.0, 550.0, 590.0],
+    [420.0, 460.0, 500.0],
+];
+
+// A transparent film a few hundred nanometres thick on top of a surface, like a soap
+// bubble's wall, oil on water or the oxide on heated steel. Light reflected off the top of the
+// film and off the surface below interferes, so some wavelengths cancel out and others add
+// up, depending on the film's thickness and the angle it is seen at.
+#[derive(Clone)]
+pub struct ThinFilm {
+    ior: f64,
+    thickness: Box<TexEnum>,
+    thinnest: f64,
+    thickest: f64,
+}
+
+impl ThinFilm {
+    pub fn from(ior: f64, thickness: f64) -> ThinFilm {
+        // Thickness in nanometres.
+        ThinFilm::from_texture(
+            ior,
+            TexEnum::SolidColor(SolidColor::from_rgb(0.0, 0.0, 0.0)),
+            thickness,
+            thickness,
+        )
+    }
+    pub fn from_texture(ior: f64, thickness: TexEnum, thinnest: f64, thickest: f64) -> ThinFilm {
+        // The texture's red channel picks a thickness between thinnest, at 0, and thickest,
+        // at 1, in nanometres.
+        ThinFilm {
+            ior,
+            thickness: Box::new(thickness),
+            thinnest,
+            thickest,
+        }
+    }
+
+    pub fn reflectance(
+        &self,
+        rec: &HitRecord,
+        cos_theta: f64,
+        outside: f64,
+        eta: &Color,
+        k: &Color,
+    ) -> Color {
+        // Unpolarized reflectance of the film over a surface with complex index of refraction
+        // eta + ik, for light arriving at cos_theta through a medium of index `outside`.
+        let t = f64::clamp(self.thickness.value(rec.u, rec.v, &rec.p).x(), 0.0, 1.0);
+        let thickness = self.thinnest + t * (self.thickest - self.thinnest);
+        let channel = |c: usize| {
+            let substrate = Complex::from(eta[c], k[c]);
+            WAVELENGTHS[c]
+                .iter()
+                .map(|&wavelength| {
+                    airy(
+                        f64::clamp(cos_theta, 0.0, 1.0),
+                        outside,
+                        self.ior,
+                        substrate,
+                        thickness,
+                        wavelength,
+                    )
+                })
+                .sum::<f64>()
+                / 3.0
+        };
+        Color::from(channel(0), channel(1), channel(2))
+    }
+}
+
+pub fn ior_from_reflectance(f0: &Color) -> Color {
+    // The index of refraction of a dielectric reflecting f0 head on, for surfaces given by
+    // their color rather than a measured index.
+    let ior = |f: f64| {
+        let r = f64::sqrt(f64::clamp(f, 0.0, 0.99));
+        (1.0 + r) / (1.0 - r)
+    };
+    Color::from(ior(f0.x()), ior(f0.y()), ior(f0.z()))
+}
+
+fn airy(
+    cos_theta: f64,
+    outside: f64,
+    film: f64,
+    substrate: Complex,
+    thickness: f64,
+    wavelength: f64,
+) -> f64 {
+    // Sums the light bouncing back and forth inside the film, after Born and Wolf, "Principles
+    // of Optics", 1.6.4. The cosines in the film and below follow from Snell's law; they
+    // become complex past the critical angle and inside metals.
+    let n1 = Complex::from(outside, 0.0);
+    let n2 = Complex::from(film, 0.0);
+    let sin2 = Complex::from(outside * outside * (1.0 - cos_theta * cos_theta), 0.0);
+    let layer_cos = |n: Complex| (Complex::from(1.0, 0.0) - sin2 / (n * n)).sqrt();
+    let cos1 = Complex::from(cos_theta, 0.0);
+    let cos2 = layer_cos(n2);
+    let cos3 = layer_cos(substrate);
+
+    // The phase the light picks up going down through the film and back up.
+    let phase = n2 * cos2 * Complex::from(4.0 * PI * thickness / wavelength, 0.0);
+    let shift = (Complex::from(0.0, 1.0) * phase).exp();
+
+    let s = |ni: Complex, ci: Complex, nt: Complex, ct: Complex| {
+        (ni * ci - nt * ct) / (ni * ci + nt * ct)
+    };
+    let p = |ni: Complex, ci: Complex, nt: Complex, ct: Complex| {
+        (nt * ci - ni * ct) / (nt * ci + ni * ct)
+    };
+    let total = |r12: Complex, r23: Complex| {
+        let r = (r12 + r23 * shift) / (Complex::from(1.0, 0.0) + r12 * r23 * shift);
+        r.norm_sqr()
+    };
+    let rs = total(s(n1, cos1, n2, cos2), s(n2, cos2, substrate, cos3));
+    let rp = total(p(n1, cos1, n2, cos2), p(n2, cos2, substrate, cos3));
+    f64::min((rs + rp) / 2.0, 1.0)
+}
+
+#[derive(Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn from(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    fn sqrt(self) -> Complex {
+        // The root with a non-negative real part.
+        let r = f64::sqrt(self.norm_sqr());
+        let re = f64::sqrt(f64::max(0.0, (r + self.re) / 2.0));
+        let im = f64::sqrt(f64::max(0.0, (r - self.re) / 2.0));
+        Complex::from(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    fn exp(self) -> Complex {
+        let m = f64::exp(self.re);
+        Complex::from(m * f64::cos(self.im), m * f64::sin(self.im))
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Self::Output {
+        Complex::from(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Self::Output {
+        Complex::from(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Self::Output {
+        Complex::from(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: Complex) -> Self::Output {
+        let d = rhs.norm_sqr();
+        Complex::from(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
@@ -0,0 +1,59 @@
+// Walker/Vose alias table: draws an index in proportion to a list of weights in constant time.
+#[derive(Clone)]
+pub struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+    pmf: Vec<f64>,
+}
+
+impl AliasTable {
+    pub fn from(weights: &[f64]) -> AliasTable {
+        // Weights must be non-negative; if they are all zero every index is equally likely.
+        let n = weights.len();
+        let total: f64 = weights.iter().sum();
+        let pmf: Vec<f64> = if total > 0.0 {
+            weights.iter().map(|w| w / total).collect()
+        } else {
+            vec![1.0 / n as f64; n]
+        };
+
+        // Split the scaled weights into those below and above the average, then pair each
+        // small entry with a large one that tops it up.
+        let mut prob: Vec<f64> = pmf.iter().map(|p| p * n as f64).collect();
+        let mut alias: Vec<usize> = (0..n).collect();
+        let mut small: Vec<usize> = (0..n).filter(|&i| prob[i] < 1.0).collect();
+        let mut large: Vec<usize> = (0..n).filter(|&i| prob[i] >= 1.0).collect();
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            prob[l] -= 1.0 - prob[s];
+            if prob[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left over is 1 up to rounding.
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+
+        AliasTable { prob, alias, pmf }
+    }
+
+    pub fn pmf(&self, i: usize) -> f64 {
+        self.pmf[i]
+    }
+
+    pub fn sample(&self, u: f64) -> usize {
+        // Maps u in [0, 1) to an index; the integer part picks a bucket, the fraction decides
+        // between the bucket and its alias.
+        let n = self.pmf.len();
+        let scaled = u * n as f64;
+        let i = usize::min(scaled as usize, n - 1);
+        if scaled - (i as f64) < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
@@ -131,6 +131,15 @@ impl Hittable for BvhNode {
     fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
         Box::from((*self).clone())
     }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        // Single object nodes hold the same object on both sides.
+        let mut lights = self.left.lights();
+        if !Arc::ptr_eq(&self.left, &self.right) {
+            lights.extend(self.right.lights());
+        }
+        lights
+    }
 }
 //class BvhNode : public hittable {
 //   public:
@@ -10,6 +10,121 @@ pub fn linear_to_gamma(linear_component: f64) -> f64 {
     return linear_component.sqrt();
 }
 
+pub fn luminance(c: &Color) -> f64 {
+    // Relative luminance of a linear sRGB color.
+    0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z()
+}
+
+pub fn blackbody(kelvin: f64) -> Color {
+    // Linear sRGB color of a blackbody at the given temperature, scaled to unit luminance.
+    // Temperatures far below 1900 K fall outside the sRGB gamut and are clipped, and anything
+    // below 500 K, where nothing visibly glows, is taken as 500 K.
+    let rgb = xyz_to_rgb(&blackbody_xyz(f64::max(kelvin, 500.0)));
+    let rgb = Color::from(
+        f64::max(rgb.x(), 0.0),
+        f64::max(rgb.y(), 0.0),
+        f64::max(rgb.z(), 0.0),
+    );
+    let y = luminance(&rgb);
+    if y <= 0.0 {
+        return Color::from(0.0, 0.0, 0.0);
+    }
+    &rgb / y
+}
+
+fn blackbody_xyz(kelvin: f64) -> Vec3 {
+    // Planck's law integrated against the CIE 1931 color matching functions over the visible
+    // range, normalized to Y = 1.
+    let mut xyz = Vec3::blank();
+    let mut lambda = 380.0;
+    while lambda <= 780.0 {
+        let m = lambda * 1e-9;
+        let radiance = 1.0 / (f64::powi(m, 5) * (f64::exp(1.4387769e-2 / (m * kelvin)) - 1.0));
+        xyz += &(&cie_xyz(lambda) * radiance);
+        lambda += 5.0;
+    }
+    &xyz / xyz.y()
+}
+
+fn cie_xyz(lambda: f64) -> Vec3 {
+    // Multi-lobe Gaussian fit of the CIE 1931 observer. See Wyman, Sloan and Shirley, "Simple
+    // Analytic Approximations to the CIE XYZ Color Matching Functions".
+    let g = |mu: f64, sigma1: f64, sigma2: f64| {
+        let t = (lambda - mu) / if lambda < mu { sigma1 } else { sigma2 };
+        f64::exp(-0.5 * t * t)
+    };
+    Vec3::from(
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2),
+        0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1),
+        1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8),
+    )
+}
+
+type Matrix3 = [[f64; 3]; 3];
+
+const RGB_TO_XYZ: Matrix3 = [
+    [0.4124, 0.3576, 0.1805],
+    [0.2126, 0.7152, 0.0722],
+    [0.0193, 0.1192, 0.9505],
+];
+const XYZ_TO_RGB: Matrix3 = [
+    [3.2406, -1.5372, -0.4986],
+    [-0.9689, 1.8758, 0.0415],
+    [0.0557, -0.2040, 1.0570],
+];
+// Bradford cone response matrix and its inverse, used for chromatic adaptation.
+const XYZ_TO_LMS: Matrix3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+const LMS_TO_XYZ: Matrix3 = [
+    [0.9869929, -0.1470543, 0.1599627],
+    [0.4323053, 0.5183603, 0.0492912],
+    [-0.0085287, 0.0400428, 0.9684867],
+];
+
+fn apply(m: &Matrix3, v: &Vec3) -> Vec3 {
+    Vec3::from(
+        m[0][0] * v.x() + m[0][1] * v.y() + m[0][2] * v.z(),
+        m[1][0] * v.x() + m[1][1] * v.y() + m[1][2] * v.z(),
+        m[2][0] * v.x() + m[2][1] * v.y() + m[2][2] * v.z(),
+    )
+}
+
+fn xyz_to_rgb(xyz: &Vec3) -> Color {
+    apply(&XYZ_TO_RGB, xyz)
+}
+
+// Camera white balance: maps colors lit by a light of the given color temperature to how they
+// would look under the sRGB white, so a light at that temperature renders neutral.
+#[derive(Clone)]
+pub struct WhiteBalance {
+    source: Vec3,
+    target: Vec3,
+}
+
+impl WhiteBalance {
+    pub fn from_kelvin(kelvin: f64) -> WhiteBalance {
+        WhiteBalance {
+            source: apply(&XYZ_TO_LMS, &blackbody_xyz(kelvin)),
+            // The D65 white point of sRGB.
+            target: apply(&XYZ_TO_LMS, &Vec3::from(0.95047, 1.0, 1.08883)),
+        }
+    }
+
+    pub fn apply(&self, c: &Color) -> Color {
+        // Von Kries scaling of the Bradford cone responses.
+        let lms = apply(&XYZ_TO_LMS, &apply(&RGB_TO_XYZ, c));
+        let adapted = Vec3::from(
+            lms.x() * self.target.x() / self.source.x(),
+            lms.y() * self.target.y() / self.source.y(),
+            lms.z() * self.target.z() / self.source.z(),
+        );
+        xyz_to_rgb(&apply(&LMS_TO_XYZ, &adapted))
+    }
+}
+
 pub(crate) mod foo {
     macro_rules! fmt_to_file {
     (&mut $file:expr,$($arg:tt)*) => {
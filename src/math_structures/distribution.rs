@@ -0,0 +1,99 @@
+// Piecewise-constant distribution over [0, 1) with one bucket per weight, for sampling
+// continuous quantities in proportion to tabulated values.
+#[derive(Clone)]
+pub struct Distribution1D {
+    func: Vec<f64>,
+    cdf: Vec<f64>,
+    integral: f64,
+}
+
+impl Distribution1D {
+    pub fn from(weights: &[f64]) -> Distribution1D {
+        // Weights must be non-negative; if they are all zero the distribution is uniform.
+        let n = weights.len();
+        let mut cdf = vec![0.0; n + 1];
+        for i in 0..n {
+            cdf[i + 1] = cdf[i] + weights[i] / n as f64;
+        }
+        let integral = cdf[n];
+        for (i, c) in cdf.iter_mut().enumerate() {
+            *c = if integral > 0.0 {
+                *c / integral
+            } else {
+                i as f64 / n as f64
+            };
+        }
+        Distribution1D {
+            func: weights.to_vec(),
+            cdf,
+            integral,
+        }
+    }
+
+    pub fn integral(&self) -> f64 {
+        self.integral
+    }
+
+    pub fn pdf(&self, x: f64) -> f64 {
+        // Density at x in [0, 1).
+        let n = self.func.len();
+        let i = usize::min((x * n as f64) as usize, n - 1);
+        if self.integral > 0.0 {
+            self.func[i] / self.integral
+        } else {
+            1.0
+        }
+    }
+
+    pub fn sample(&self, u: f64) -> (f64, usize) {
+        // Maps u in [0, 1) to a point in [0, 1) and the bucket it falls in by inverting the
+        // cdf, interpolating linearly inside the bucket.
+        let n = self.func.len();
+        let i = usize::min(self.cdf.partition_point(|&c| c <= u), n) - 1;
+        let width = self.cdf[i + 1] - self.cdf[i];
+        let du = if width > 0.0 {
+            (u - self.cdf[i]) / width
+        } else {
+            0.0
+        };
+        (f64::min((i as f64 + du) / n as f64, 1.0 - 1e-12), i)
+    }
+}
+
+// Piecewise-constant distribution over [0, 1)^2 given as rows of weights: a row is picked from
+// the marginal distribution, then a column from that row.
+#[derive(Clone)]
+pub struct Distribution2D {
+    rows: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    pub fn from(weights: &[f64], width: usize, height: usize) -> Distribution2D {
+        let rows: Vec<Distribution1D> = weights
+            .chunks(width)
+            .take(height)
+            .map(Distribution1D::from)
+            .collect();
+        let marginal =
+            Distribution1D::from(&rows.iter().map(|r| r.integral()).collect::<Vec<f64>>());
+        Distribution2D { rows, marginal }
+    }
+
+    pub fn integral(&self) -> f64 {
+        self.marginal.integral()
+    }
+
+    pub fn pdf(&self, u: f64, v: f64) -> f64 {
+        // Density at (u, v), where u runs along a row and v picks the row.
+        let n = self.rows.len();
+        let row = usize::min((v * n as f64) as usize, n - 1);
+        self.marginal.pdf(v) * self.rows[row].pdf(u)
+    }
+
+    pub fn sample(&self, s: f64, t: f64) -> (f64, f64) {
+        let (v, row) = self.marginal.sample(t);
+        let (u, _) = self.rows[row].sample(s);
+        (u, v)
+    }
+}
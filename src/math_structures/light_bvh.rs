@@ -0,0 +1,342 @@
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable_list::HittableList;
+use crate::math_structures::aabb::Aabb;
+use crate::math_structures::interval::Interval;
+use crate::math_structures::ray::Ray;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, INFINITY, PI};
+use std::sync::Arc;
+
+// The set of directions light leaves an emitter in: normals lie within `theta_o` of the axis
+// `w`, and light leaves each normal within `theta_e` of it.
+#[derive(Clone, Copy)]
+pub struct LightCone {
+    pub w: Vec3,
+    pub theta_o: f64,
+    pub theta_e: f64,
+}
+
+impl LightCone {
+    pub fn from(w: Vec3, theta_o: f64, theta_e: f64) -> LightCone {
+        LightCone {
+            w: Vec3::unit_vector(&w),
+            theta_o,
+            theta_e,
+        }
+    }
+
+    pub fn all() -> LightCone {
+        // Emits in every direction, e.g. spheres and boxes.
+        LightCone::from(Vec3::from(0.0, 0.0, 1.0), PI, PI / 2.0)
+    }
+
+    pub fn one_sided(normal: &Vec3) -> LightCone {
+        // A flat diffuse emitter lighting the half space its normal points into.
+        LightCone::from(*normal, 0.0, PI / 2.0)
+    }
+
+    pub fn union(a: &LightCone, b: &LightCone) -> LightCone {
+        // Smallest cone around both normal cones; the emission spread is the wider of the two.
+        let theta_e = f64::max(a.theta_e, b.theta_e);
+        if a.theta_o >= PI || b.theta_o >= PI {
+            return LightCone::from(a.w, PI, theta_e);
+        }
+        let theta_d = angle_between(&a.w, &b.w);
+        if f64::min(theta_d + b.theta_o, PI) <= a.theta_o {
+            return LightCone::from(a.w, a.theta_o, theta_e);
+        }
+        if f64::min(theta_d + a.theta_o, PI) <= b.theta_o {
+            return LightCone::from(b.w, b.theta_o, theta_e);
+        }
+
+        let theta_o = (a.theta_o + theta_d + b.theta_o) / 2.0;
+        let axis = Vec3::cross(&a.w, &b.w);
+        if theta_o >= PI || axis.length_squared() < 1e-20 {
+            return LightCone::from(a.w, PI, theta_e);
+        }
+        // Rotate a's axis towards b's until the new cone just covers both.
+        let w = rotate(&a.w, &Vec3::unit_vector(&axis), theta_o - a.theta_o);
+        LightCone::from(w, theta_o, theta_e)
+    }
+}
+
+fn angle_between(v1: &Vec3, v2: &Vec3) -> f64 {
+    f64::acos(f64::clamp(Vec3::dot(v1, v2), -1.0, 1.0))
+}
+
+fn rotate(v: &Vec3, axis: &Vec3, angle: f64) -> Vec3 {
+    // Rodrigues' rotation of v about the unit vector axis.
+    let (sin, cos) = f64::sin_cos(angle);
+    &(&(cos * v) + &(sin * &Vec3::cross(axis, v))) + &((Vec3::dot(axis, v) * (1.0 - cos)) * axis)
+}
+
+// Flattened tree node. Interior nodes store their first child right after themselves and the
+// second at `second`; leaves store the index of their light.
+#[derive(Clone)]
+struct LightNode {
+    bbox: Aabb,
+    power: f64,
+    cone: LightCone,
+    second: usize,
+    light: Option<usize>,
+}
+
+// A hierarchy over a set of lights for importance sampling many of them. Each step down the
+// tree picks a child in proportion to how much light it could send to the shading point,
+// judged from its power, distance and orientation.
+#[derive(Clone)]
+pub struct LightBvh {
+    lights: Vec<Arc<dyn Hittable + Send + Sync>>,
+    nodes: Vec<LightNode>,
+}
+
+impl LightBvh {
+    pub fn from_list(list: &HittableList) -> LightBvh {
+        let lights = list.objects.clone();
+
+        // Lights of unknown power get the average of the others so they are still sampled.
+        let mut powers: Vec<f64> = lights.iter().map(|l| l.power()).collect();
+        let known: Vec<f64> = powers.iter().copied().filter(|&p| p > 0.0).collect();
+        let average = if known.is_empty() {
+            1.0
+        } else {
+            known.iter().sum::<f64>() / known.len() as f64
+        };
+        for p in powers.iter_mut().filter(|p| **p <= 0.0) {
+            *p = average;
+        }
+
+        let mut this = LightBvh {
+            lights,
+            nodes: vec![],
+        };
+        let mut indices: Vec<usize> = (0..this.lights.len()).collect();
+        if !indices.is_empty() {
+            this.build(&mut indices, &powers);
+        }
+        this
+    }
+
+    fn build(&mut self, indices: &mut [usize], powers: &[f64]) -> usize {
+        let node = self.nodes.len();
+        if indices.len() == 1 {
+            let light = &self.lights[indices[0]];
+            self.nodes.push(LightNode {
+                bbox: light.bounding_box(),
+                power: powers[indices[0]],
+                cone: light.emission_cone(),
+                second: 0,
+                light: Some(indices[0]),
+            });
+            return node;
+        }
+
+        // Split at the median along the axis with the widest spread of light centers.
+        let centers: Vec<Point3> = indices
+            .iter()
+            .map(|&i| center(&self.lights[i].bounding_box()))
+            .collect();
+        let mut extent = [0.0; 3];
+        for (a, e) in extent.iter_mut().enumerate() {
+            let min = centers.iter().map(|c| c[a]).fold(INFINITY, f64::min);
+            let max = centers.iter().map(|c| c[a]).fold(-INFINITY, f64::max);
+            *e = max - min;
+        }
+        let axis = if extent[0] > extent[1] && extent[0] > extent[2] {
+            0
+        } else if extent[1] > extent[2] {
+            1
+        } else {
+            2
+        };
+        let lights = &self.lights;
+        indices.sort_by(|&a, &b| {
+            f64::total_cmp(
+                &center(&lights[a].bounding_box())[axis],
+                &center(&lights[b].bounding_box())[axis],
+            )
+        });
+
+        self.nodes.push(LightNode {
+            bbox: Aabb::blank(),
+            power: 0.0,
+            cone: LightCone::all(),
+            second: 0,
+            light: None,
+        });
+        let mid = indices.len() / 2;
+        let (left_indices, right_indices) = indices.split_at_mut(mid);
+        let left = self.build(left_indices, powers);
+        let right = self.build(right_indices, powers);
+
+        let (l, r) = (&self.nodes[left], &self.nodes[right]);
+        let bbox = Aabb::from_aabbs(&l.bbox, &r.bbox);
+        let power = l.power + r.power;
+        let cone = LightCone::union(&l.cone, &r.cone);
+        self.nodes[node] = LightNode {
+            bbox,
+            power,
+            cone,
+            second: right,
+            light: None,
+        };
+        node
+    }
+
+    fn importance(&self, node: usize, p: &Point3) -> f64 {
+        // Conservative estimate of the light a node could send to p: its power over the squared
+        // distance, scaled by the cosine of the smallest angle between the emission cone and
+        // the direction to p.
+        let n = &self.nodes[node];
+        let c = center(&n.bbox);
+        let radius = 0.5
+            * (&Point3::from(n.bbox.x.max, n.bbox.y.max, n.bbox.z.max)
+                - &Point3::from(n.bbox.x.min, n.bbox.y.min, n.bbox.z.min))
+                .length();
+        let to_p = p - &c;
+        let distance_squared = f64::max(to_p.length_squared(), radius * radius);
+        let distance = to_p.length();
+
+        // Angle the bounds subtend from p; from inside them every direction is possible.
+        let theta_b = if distance <= radius {
+            PI
+        } else {
+            f64::asin(radius / distance)
+        };
+        let theta_w = if distance > 0.0 {
+            angle_between(&n.cone.w, &(&to_p / distance))
+        } else {
+            0.0
+        };
+        let theta = f64::max(theta_w - n.cone.theta_o - theta_b, 0.0);
+        if theta >= n.cone.theta_e {
+            return 0.0;
+        }
+        n.power * f64::max(f64::cos(theta), 0.0) / distance_squared
+    }
+
+    fn child_probabilities(&self, node: usize, p: &Point3) -> (f64, f64) {
+        let i0 = self.importance(node + 1, p);
+        let i1 = self.importance(self.nodes[node].second, p);
+        if i0 + i1 <= 0.0 {
+            return (0.5, 0.5);
+        }
+        (i0 / (i0 + i1), i1 / (i0 + i1))
+    }
+}
+
+fn center(bbox: &Aabb) -> Point3 {
+    Point3::from(
+        0.5 * (bbox.x.min + bbox.x.max),
+        0.5 * (bbox.y.min + bbox.y.max),
+        0.5 * (bbox.z.min + bbox.z.max),
+    )
+}
+
+impl Hittable for LightBvh {
+    fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let mut closest_so_far = ray_t.max;
+        let mut rec = None;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(node) = stack.pop() {
+            let n = &self.nodes[node];
+            let t_range = Interval::from(ray_t.min, closest_so_far);
+            if n.bbox.hit(r, &t_range).is_none() {
+                continue;
+            }
+            match n.light {
+                Some(i) => {
+                    if let Some(x) = self.lights[i].hit(r, &t_range) {
+                        closest_so_far = x.t;
+                        rec = Some(x);
+                    }
+                }
+                None => {
+                    stack.push(node + 1);
+                    stack.push(n.second);
+                }
+            }
+        }
+        rec
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        match self.nodes.first() {
+            None => Aabb::blank(),
+            Some(n) => n.bbox.clone(),
+        }
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
+        Box::new((*self).clone())
+    }
+
+    fn pdf_value(&self, o: &Point3, v: &Vec3, time: f64) -> f64 {
+        // Sum over the lights the direction could reach, each weighted by the probability of
+        // walking down the tree to it from o.
+        let r = Ray::from_set_time(*o, *v, time);
+        let ray_t = Interval::from(0.001, INFINITY);
+        let mut sum = 0.0;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![(0, 1.0)]
+        };
+        while let Some((node, prob)) = stack.pop() {
+            let n = &self.nodes[node];
+            if n.bbox.hit(&r, &ray_t).is_none() {
+                continue;
+            }
+            match n.light {
+                Some(i) => sum += prob * self.lights[i].pdf_value(o, v, time),
+                None => {
+                    let (p0, p1) = self.child_probabilities(node, o);
+                    if p0 > 0.0 {
+                        stack.push((node + 1, prob * p0));
+                    }
+                    if p1 > 0.0 {
+                        stack.push((n.second, prob * p1));
+                    }
+                }
+            }
+        }
+        sum
+    }
+
+    fn random(&self, o: &Vec3, time: f64) -> Vec3 {
+        if self.nodes.is_empty() {
+            return Vec3::blank();
+        }
+        let mut node = 0;
+        loop {
+            let n = &self.nodes[node];
+            if let Some(i) = n.light {
+                return self.lights[i].random(o, time);
+            }
+            let (p0, _) = self.child_probabilities(node, o);
+            node = if random_double() < p0 {
+                node + 1
+            } else {
+                n.second
+            };
+        }
+    }
+
+    fn power(&self) -> f64 {
+        match self.nodes.first() {
+            None => 0.0,
+            Some(n) => n.power,
+        }
+    }
+
+    fn emission_cone(&self) -> LightCone {
+        match self.nodes.first() {
+            None => LightCone::all(),
+            Some(n) => n.cone,
+        }
+    }
+}
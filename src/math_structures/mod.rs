@@ -1,7 +1,11 @@
 pub mod aabb;
+pub mod alias_table;
 pub mod bvh;
 pub mod color;
+pub mod distribution;
 pub mod interval;
+pub mod light_bvh;
 pub mod onb;
 pub mod ray;
+pub mod spherical;
 pub mod vec3;
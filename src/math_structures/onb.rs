@@ -1,6 +1,7 @@
 use crate::math_structures::vec3::Vec3;
 use std::ops::Index;
 
+#[derive(Clone)]
 pub struct Onb {
     axis: [Vec3; 3],
 }
@@ -26,6 +27,14 @@ impl Onb {
         &(&(a.x() * self.u()) + &(a.y() * self.v())) + &(a.z() * self.w())
     }
 
+    pub fn vec3_to_local(&self, a: &Vec3) -> Vec3 {
+        Vec3::from(
+            Vec3::dot(a, self.u()),
+            Vec3::dot(a, self.v()),
+            Vec3::dot(a, self.w()),
+        )
+    }
+
     pub fn build_from_w(&mut self, w: &Vec3) {
         let unit_w = Vec3::unit_vector(w);
         let a = if f64::abs(unit_w.x()) > 0.9 {
@@ -39,6 +48,21 @@ impl Onb {
         self.axis[1] = v;
         self.axis[2] = unit_w;
     }
+
+    pub fn build_from_w_v(&mut self, w: &Vec3, v: &Vec3) {
+        // Like `build_from_w`, but with v pointing along the part of the given v that lies
+        // across w, so the frame stays put as w moves. Falls back when v is parallel to w.
+        let unit_w = Vec3::unit_vector(w);
+        let across = v - &(Vec3::dot(v, &unit_w) * &unit_w);
+        if across.length_squared() <= 1e-12 * v.length_squared() {
+            self.build_from_w(w);
+            return;
+        }
+        let unit_v = Vec3::unit_vector(&across);
+        self.axis[0] = Vec3::cross(&unit_v, &unit_w);
+        self.axis[1] = unit_v;
+        self.axis[2] = unit_w;
+    }
 }
 
 impl Index<usize> for Onb {
@@ -0,0 +1,193 @@
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::PI;
+
+// Below this solid angle the spherical samplers lose precision and area sampling is just as
+// good; above the upper bound the shape surrounds the origin.
+pub const MIN_SPHERICAL_SOLID_ANGLE: f64 = 3e-4;
+pub const MAX_SPHERICAL_SOLID_ANGLE: f64 = 6.22;
+
+// The solid angle subtended by a rectangle, set up for uniform sampling over it.
+// See Urena, Fajardo and King, "An Area-Preserving Parametrization for Spherical Rectangles".
+pub struct SphericalRectangle {
+    o: Point3,
+    ex: Vec3,
+    ey: Vec3,
+    ez: Vec3,
+    x0: f64,
+    y0: f64,
+    z0: f64,
+    x1: f64,
+    y1: f64,
+    b0: f64,
+    b1: f64,
+    k: f64,
+    pub solid_angle: f64,
+}
+
+impl SphericalRectangle {
+    pub fn from(o: &Point3, q: &Point3, u: &Vec3, v: &Vec3) -> SphericalRectangle {
+        // The rectangle is q + a*u + b*v for a, b in [0, 1]; u and v must be orthogonal.
+        let ex = Vec3::unit_vector(u);
+        let ey = Vec3::unit_vector(v);
+        let mut ez = Vec3::cross(&ex, &ey);
+        let d = q - o;
+        let x0 = Vec3::dot(&d, &ex);
+        let y0 = Vec3::dot(&d, &ey);
+        let mut z0 = Vec3::dot(&d, &ez);
+        // Work in the frame where the rectangle lies below the origin.
+        if z0 > 0.0 {
+            z0 = -z0;
+            ez = -&ez;
+        }
+        let x1 = x0 + u.length();
+        let y1 = y0 + v.length();
+
+        let v00 = Vec3::from(x0, y0, z0);
+        let v01 = Vec3::from(x0, y1, z0);
+        let v10 = Vec3::from(x1, y0, z0);
+        let v11 = Vec3::from(x1, y1, z0);
+        let n0 = Vec3::unit_vector(&Vec3::cross(&v00, &v10));
+        let n1 = Vec3::unit_vector(&Vec3::cross(&v10, &v11));
+        let n2 = Vec3::unit_vector(&Vec3::cross(&v11, &v01));
+        let n3 = Vec3::unit_vector(&Vec3::cross(&v01, &v00));
+
+        // Interior angles of the spherical quadrilateral.
+        let g0 = f64::acos(f64::clamp(-Vec3::dot(&n0, &n1), -1.0, 1.0));
+        let g1 = f64::acos(f64::clamp(-Vec3::dot(&n1, &n2), -1.0, 1.0));
+        let g2 = f64::acos(f64::clamp(-Vec3::dot(&n2, &n3), -1.0, 1.0));
+        let g3 = f64::acos(f64::clamp(-Vec3::dot(&n3, &n0), -1.0, 1.0));
+        let k = 2.0 * PI - g2 - g3;
+
+        SphericalRectangle {
+            o: *o,
+            ex,
+            ey,
+            ez,
+            x0,
+            y0,
+            z0,
+            x1,
+            y1,
+            b0: n0.z(),
+            b1: n2.z(),
+            k,
+            solid_angle: g0 + g1 - k,
+        }
+    }
+
+    pub fn sample(&self, s: f64, t: f64) -> Point3 {
+        // Maps (s, t) in [0, 1)^2 to a point on the rectangle, uniform in solid angle.
+        let au = s * self.solid_angle + self.k;
+        let fu = (f64::cos(au) * self.b0 - self.b1) / f64::sin(au);
+        let cu = f64::clamp(
+            f64::signum(fu) / f64::sqrt(fu * fu + self.b0 * self.b0),
+            -1.0,
+            1.0,
+        );
+        let xu = f64::clamp(
+            -(cu * self.z0) / f64::sqrt(f64::max(1.0 - cu * cu, 1e-12)),
+            self.x0,
+            self.x1,
+        );
+
+        let d = f64::sqrt(xu * xu + self.z0 * self.z0);
+        let h0 = self.y0 / f64::sqrt(d * d + self.y0 * self.y0);
+        let h1 = self.y1 / f64::sqrt(d * d + self.y1 * self.y1);
+        let hv = h0 + t * (h1 - h0);
+        let yv = if hv * hv < 1.0 - 1e-12 {
+            hv * d / f64::sqrt(1.0 - hv * hv)
+        } else {
+            self.y1
+        };
+
+        &self.o + &(&(&(xu * &self.ex) + &(yv * &self.ey)) + &(self.z0 * &self.ez))
+    }
+}
+
+// The solid angle subtended by a triangle, set up for uniform sampling over it.
+// See Arvo, "Stratified Sampling of Spherical Triangles".
+pub struct SphericalTriangle {
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+    alpha: f64,
+    pub solid_angle: f64,
+}
+
+impl SphericalTriangle {
+    pub fn from(o: &Point3, p0: &Point3, p1: &Point3, p2: &Point3) -> Option<SphericalTriangle> {
+        // Returns None when the triangle is degenerate as seen from o.
+        let a = Vec3::unit_vector(&(p0 - o));
+        let b = Vec3::unit_vector(&(p1 - o));
+        let c = Vec3::unit_vector(&(p2 - o));
+
+        let n_ab = Vec3::cross(&a, &b);
+        let n_bc = Vec3::cross(&b, &c);
+        let n_ca = Vec3::cross(&c, &a);
+        if n_ab.length_squared() < 1e-20
+            || n_bc.length_squared() < 1e-20
+            || n_ca.length_squared() < 1e-20
+        {
+            return None;
+        }
+        let n_ab = Vec3::unit_vector(&n_ab);
+        let n_bc = Vec3::unit_vector(&n_bc);
+        let n_ca = Vec3::unit_vector(&n_ca);
+
+        let alpha = angle_between(&n_ab, &-&n_ca);
+        let beta = angle_between(&n_bc, &-&n_ab);
+        let gamma = angle_between(&n_ca, &-&n_bc);
+
+        Some(SphericalTriangle {
+            a,
+            b,
+            c,
+            alpha,
+            solid_angle: alpha + beta + gamma - PI,
+        })
+    }
+
+    pub fn sample(&self, s: f64, t: f64) -> Vec3 {
+        // Maps (s, t) in [0, 1)^2 to a unit direction, uniform in solid angle.
+        // First pick the sub-triangle with area s * solid_angle, which fixes the point c'
+        // on edge ac, then pick a point along the arc from b to c'.
+        let area_pi = PI + s * self.solid_angle;
+        let (sin_alpha, cos_alpha) = f64::sin_cos(self.alpha);
+        let sin_phi = f64::sin(area_pi) * cos_alpha - f64::cos(area_pi) * sin_alpha;
+        let cos_phi = f64::cos(area_pi) * cos_alpha + f64::sin(area_pi) * sin_alpha;
+
+        let k1 = cos_phi + cos_alpha;
+        let k2 = sin_phi - sin_alpha * Vec3::dot(&self.a, &self.b);
+        let cos_bp = f64::clamp(
+            (k2 + (k2 * cos_phi - k1 * sin_phi) * cos_alpha)
+                / ((k2 * sin_phi + k1 * cos_phi) * sin_alpha),
+            -1.0,
+            1.0,
+        );
+        let sin_bp = f64::sqrt(1.0 - cos_bp * cos_bp);
+        let cp = &(cos_bp * &self.a)
+            + &(sin_bp * &Vec3::unit_vector(&orthogonal_part(&self.c, &self.a)));
+
+        let cos_theta = 1.0 - t * (1.0 - Vec3::dot(&cp, &self.b));
+        let sin_theta = f64::sqrt(f64::max(1.0 - cos_theta * cos_theta, 0.0));
+        let w = orthogonal_part(&cp, &self.b);
+        if w.length_squared() < 1e-20 {
+            return self.b;
+        }
+        &(cos_theta * &self.b) + &(sin_theta * &Vec3::unit_vector(&w))
+    }
+}
+
+fn angle_between(v1: &Vec3, v2: &Vec3) -> f64 {
+    // Numerically stable angle between two unit vectors.
+    if Vec3::dot(v1, v2) < 0.0 {
+        PI - 2.0 * f64::asin(f64::min((v1 + v2).length() / 2.0, 1.0))
+    } else {
+        2.0 * f64::asin(f64::min((v2 - v1).length() / 2.0, 1.0))
+    }
+}
+
+fn orthogonal_part(v: &Vec3, w: &Vec3) -> Vec3 {
+    // The component of v orthogonal to the unit vector w.
+    v - &(Vec3::dot(v, w) * w)
+}
@@ -18,6 +18,7 @@ pub fn render_to_memory(
     pixel_pipe: Sender<(i64, i64, Color)>,
 ) -> Vec<String> {
     let start_time = Instant::now();
+    let lights = Arc::new(camera.scene_lights(&world, &lights));
     let pool = ThreadPool::new(NUM_OF_ACTIVE_THREADS);
     let (tx, rx) = channel();
 
@@ -85,9 +86,12 @@ pub fn thread_render(
         for s_j in 0..(cam.sqrt_spp as i64) {
             for s_i in 0..(cam.sqrt_spp as i64) {
                 let r = cam.get_ray(i, j, s_i, s_j);
-                pixel_color += &cam.ray_color(&r, cam.max_depth, &world, &lights);
+                pixel_color += &cam.ray_color(&r, cam.max_depth, &world, &lights, &[]);
             }
         }
+        if let Some(wb) = &cam.white_balance {
+            pixel_color = wb.apply(&pixel_color);
+        }
         let pixel = color_post_processing(&pixel_color, cam.samples_per_pixel);
         let _x = pixel_pipe.send((i, j, pixel));
         s += &*format!(
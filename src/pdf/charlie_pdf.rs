@@ -0,0 +1,50 @@
+use crate::materials::microfacet::charlie_d;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::pdf::Pdf;
+use crate::rtweekend::{random_double, PI};
+
+// Mirror reflections off microfacet normals drawn from the Charlie sheen distribution, for
+// cloth. `uvw` is the local frame of the surface, `wo` the outgoing direction in it and alpha
+// the distribution's roughness.
+pub struct CharliePdf {
+    uvw: Onb,
+    wo: Vec3,
+    alpha: f64,
+}
+
+impl CharliePdf {
+    pub fn from(uvw: Onb, wo: Vec3, alpha: f64) -> CharliePdf {
+        CharliePdf { uvw, wo, alpha }
+    }
+}
+
+impl Pdf for CharliePdf {
+    fn value(&self, direction: &Vec3) -> f64 {
+        let wi = self.uvw.vec3_to_local(&Vec3::unit_vector(direction));
+        if wi.z() <= 0.0 {
+            return 0.0;
+        }
+        let wm = &self.wo + &wi;
+        if wm.length_squared() <= 0.0 {
+            return 0.0;
+        }
+        let wm = Vec3::unit_vector(&wm);
+        charlie_d(self.alpha, &wm) * wm.z() / (4.0 * f64::abs(Vec3::dot(&self.wo, &wm)))
+    }
+
+    fn generate(&self) -> Vec3 {
+        // The distribution times the cosine integrates to sin^(2 + 1 / alpha) of the angle
+        // from the normal, which is inverted to pick the angle.
+        let sin_theta = f64::powf(random_double(), 1.0 / (2.0 + 1.0 / self.alpha));
+        let cos_theta = f64::sqrt(f64::max(0.0, 1.0 - sin_theta * sin_theta));
+        let phi = 2.0 * PI * random_double();
+        let wm = Vec3::from(
+            sin_theta * f64::cos(phi),
+            sin_theta * f64::sin(phi),
+            cos_theta,
+        );
+        let wi = &(2.0 * Vec3::dot(&self.wo, &wm) * &wm) - &self.wo;
+        self.uvw.local_from_vec3(&wi)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::materials::microfacet::TrowbridgeReitz;
+use crate::math_structures::onb::Onb;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::pdf::Pdf;
+use crate::rtweekend::random_double;
+
+// Mirror reflections off microfacet normals drawn from the part of a GGX distribution visible
+// from the outgoing direction `wo`, given in the local frame `uvw` of the surface.
+pub struct GgxPdf {
+    uvw: Onb,
+    wo: Vec3,
+    distribution: TrowbridgeReitz,
+}
+
+impl GgxPdf {
+    pub fn from(uvw: Onb, wo: Vec3, distribution: TrowbridgeReitz) -> GgxPdf {
+        GgxPdf {
+            uvw,
+            wo,
+            distribution,
+        }
+    }
+}
+
+impl Pdf for GgxPdf {
+    fn value(&self, direction: &Vec3) -> f64 {
+        let wi = self.uvw.vec3_to_local(&Vec3::unit_vector(direction));
+        if wi.z() <= 0.0 {
+            return 0.0;
+        }
+        let wm = &self.wo + &wi;
+        if wm.length_squared() <= 0.0 {
+            return 0.0;
+        }
+        let wm = Vec3::unit_vector(&wm);
+        // Reflecting maps normals to directions with a Jacobian of 1 / (4 |wo . wm|).
+        self.distribution.pdf_wm(&self.wo, &wm) / (4.0 * f64::abs(Vec3::dot(&self.wo, &wm)))
+    }
+
+    fn generate(&self) -> Vec3 {
+        let wm = self
+            .distribution
+            .sample_wm(&self.wo, random_double(), random_double());
+        let wi = &(2.0 * Vec3::dot(&self.wo, &wm) * &wm) - &self.wo;
+        self.uvw.local_from_vec3(&wi)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::math_structures::onb::Onb;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::pdf::Pdf;
+use crate::rtweekend::{random_double, PI};
+
+// The Henyey-Greenstein phase function, which scatters light off particles in a medium
+// mostly forwards along `w` for positive g, mostly backwards for negative g and evenly for 0.
+pub struct HenyeyGreensteinPdf {
+    uvw: Onb,
+    g: f64,
+}
+
+impl HenyeyGreensteinPdf {
+    pub fn from(w: &Vec3, g: f64) -> HenyeyGreensteinPdf {
+        let mut uvw = Onb::blank();
+        uvw.build_from_w(w);
+        HenyeyGreensteinPdf {
+            uvw,
+            g: f64::clamp(g, -0.99, 0.99),
+        }
+    }
+}
+
+impl Pdf for HenyeyGreensteinPdf {
+    fn value(&self, direction: &Vec3) -> f64 {
+        let cos_theta = Vec3::dot(&Vec3::unit_vector(direction), self.uvw.w());
+        let g = self.g;
+        let denom = 1.0 + g * g - 2.0 * g * cos_theta;
+        (1.0 - g * g) / (4.0 * PI * denom * f64::sqrt(denom))
+    }
+
+    fn generate(&self) -> Vec3 {
+        // Inverts the distribution's cumulative density in the cosine to w.
+        let g = self.g;
+        let u = random_double();
+        let cos_theta = if f64::abs(g) < 1e-3 {
+            1.0 - 2.0 * u
+        } else {
+            let s = (1.0 - g * g) / (1.0 - g + 2.0 * g * u);
+            (1.0 + g * g - s * s) / (2.0 * g)
+        };
+        let sin_theta = f64::sqrt(f64::max(0.0, 1.0 - cos_theta * cos_theta));
+        let phi = 2.0 * PI * random_double();
+        self.uvw.local_from_vec3(&Vec3::from(
+            sin_theta * f64::cos(phi),
+            sin_theta * f64::sin(phi),
+            cos_theta,
+        ))
+    }
+}
@@ -3,23 +3,30 @@ use crate::hittables::hittable_list::HittableList;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::pdf::pdf::Pdf;
 
-pub struct HittablePdf {
-    objects: HittableList,
+pub struct HittablePdf<'a> {
+    objects: &'a HittableList,
     origin: Point3,
+    time: f64,
 }
 
-impl HittablePdf {
-    pub fn from(objects: HittableList, origin: Point3) -> HittablePdf {
-        HittablePdf { objects, origin }
+impl<'a> HittablePdf<'a> {
+    pub fn from(objects: &'a HittableList, origin: Point3, time: f64) -> HittablePdf<'a> {
+        // The time is that of the scattered ray, so moving lights are sampled where they are
+        // at that instant.
+        HittablePdf {
+            objects,
+            origin,
+            time,
+        }
     }
 }
 
-impl Pdf for HittablePdf {
+impl Pdf for HittablePdf<'_> {
     fn value(&self, direction: &Vec3) -> f64 {
-        self.objects.pdf_value(&self.origin, direction)
+        self.objects.pdf_value(&self.origin, direction, self.time)
     }
 
     fn generate(&self) -> Vec3 {
-        self.objects.random(&self.origin)
+        self.objects.random(&self.origin, self.time)
     }
 }
@@ -2,26 +2,45 @@ use crate::math_structures::vec3::Vec3;
 use crate::pdf::pdf::Pdf;
 use crate::rtweekend::random_double;
 
-pub struct MixturePdf {
-    p: Vec<Box<dyn Pdf>>,
+pub struct MixturePdf<'a> {
+    p: Vec<Box<dyn Pdf + 'a>>,
+    weights: Vec<f64>,
 }
 
-impl MixturePdf {
-    pub fn from(p0: Box<dyn Pdf>, p1: Box<dyn Pdf>) -> MixturePdf {
-        MixturePdf { p: vec![p0, p1] }
+impl<'a> MixturePdf<'a> {
+    pub fn from(p0: Box<dyn Pdf + 'a>, p1: Box<dyn Pdf + 'a>) -> MixturePdf<'a> {
+        MixturePdf {
+            p: vec![p0, p1],
+            weights: vec![0.5, 0.5],
+        }
+    }
+    pub fn weighted(lobes: Vec<(f64, Box<dyn Pdf + 'a>)>) -> MixturePdf<'a> {
+        // Picks each pdf in proportion to its weight. Pdfs with no weight are left out.
+        let lobes: Vec<(f64, Box<dyn Pdf + 'a>)> =
+            lobes.into_iter().filter(|l| l.0 > 0.0).collect();
+        let total: f64 = lobes.iter().map(|l| l.0).sum();
+        let (weights, p) = lobes.into_iter().map(|(w, p)| (w / total, p)).unzip();
+        MixturePdf { p, weights }
     }
 }
 
-impl Pdf for MixturePdf {
+impl Pdf for MixturePdf<'_> {
     fn value(&self, direction: &Vec3) -> f64 {
-        0.5 * self.p[0].value(direction) + 0.5 * self.p[1].value(direction)
+        self.p
+            .iter()
+            .zip(&self.weights)
+            .map(|(p, w)| w * p.value(direction))
+            .sum()
     }
 
     fn generate(&self) -> Vec3 {
-        return if random_double() < 0.5 {
-            self.p[0].generate()
-        } else {
-            self.p[1].generate()
-        };
+        let mut r = random_double();
+        for (p, w) in self.p.iter().zip(&self.weights) {
+            if r < *w {
+                return p.generate();
+            }
+            r -= w;
+        }
+        self.p.last().unwrap().generate()
     }
 }
@@ -1,5 +1,9 @@
+pub mod charlie_pdf;
 pub mod cosine_pdf;
+pub mod ggx_pdf;
+pub mod henyey_greenstein_pdf;
 pub mod hittable_pdf;
 pub mod mixture_pdf;
 pub mod pdf;
+pub mod rough_dielectric_pdf;
 pub mod sphere_pdf;
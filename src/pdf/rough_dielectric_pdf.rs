@@ -0,0 +1,77 @@
+use crate::materials::microfacet::{
+    fresnel_dielectric, refract, refraction_half_vector, TrowbridgeReitz,
+};
+use crate::math_structures::onb::Onb;
+use crate::math_structures::vec3::Vec3;
+use crate::pdf::pdf::Pdf;
+use crate::rtweekend::random_double;
+
+// Reflection or refraction off a GGX microfacet normal visible from `wo`, choosing between
+// them by the Fresnel reflectance of that microfacet. `uvw` is the local frame of the surface
+// with w on the side of `wo`, and eta is the index of refraction across the surface over the
+// one on the side of `wo`.
+pub struct RoughDielectricPdf {
+    uvw: Onb,
+    wo: Vec3,
+    eta: f64,
+    distribution: TrowbridgeReitz,
+}
+
+impl RoughDielectricPdf {
+    pub fn from(uvw: Onb, wo: Vec3, eta: f64, distribution: TrowbridgeReitz) -> RoughDielectricPdf {
+        RoughDielectricPdf {
+            uvw,
+            wo,
+            eta,
+            distribution,
+        }
+    }
+}
+
+impl Pdf for RoughDielectricPdf {
+    fn value(&self, direction: &Vec3) -> f64 {
+        // Both ways of reaching `direction` are counted, including reflections that end up
+        // below the surface and refractions that end up above it, since `generate` returns
+        // those too.
+        let wi = self.uvw.vec3_to_local(&Vec3::unit_vector(direction));
+        let mut pdf = 0.0;
+
+        let wm = &self.wo + &wi;
+        if wm.length_squared() > 0.0 {
+            let wm = Vec3::unit_vector(&wm);
+            let cos_o = Vec3::dot(&self.wo, &wm);
+            if cos_o > 0.0 {
+                pdf += fresnel_dielectric(cos_o, self.eta)
+                    * self.distribution.pdf_wm(&self.wo, &wm)
+                    / (4.0 * cos_o);
+            }
+        }
+
+        if let Some(wm) = refraction_half_vector(&self.wo, &wi, self.eta) {
+            let cos_o = Vec3::dot(&self.wo, &wm);
+            let cos_i = Vec3::dot(&wi, &wm);
+            let denom = f64::powi(cos_i + cos_o / self.eta, 2);
+            if denom > 0.0 {
+                pdf += (1.0 - fresnel_dielectric(cos_o, self.eta))
+                    * self.distribution.pdf_wm(&self.wo, &wm)
+                    * f64::abs(cos_i)
+                    / denom;
+            }
+        }
+        pdf
+    }
+
+    fn generate(&self) -> Vec3 {
+        let wm = self
+            .distribution
+            .sample_wm(&self.wo, random_double(), random_double());
+        let cos_o = Vec3::dot(&self.wo, &wm);
+        let reflected = &(2.0 * cos_o * &wm) - &self.wo;
+        let wi = if random_double() < fresnel_dielectric(cos_o, self.eta) {
+            reflected
+        } else {
+            refract(&self.wo, &wm, self.eta).unwrap_or(reflected)
+        };
+        self.uvw.local_from_vec3(&wi)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::backgrounds::gradient_background::GradientBackground;
+use crate::backgrounds::image_background::ImageBackground;
+use crate::backgrounds::texture_background::TextureBackground;
+use crate::backgrounds::BgEnum;
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::sphere::Sphere;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+// A globe, a mirror ball and a glass ball floating in front of one of the background types,
+// which is all that lights them.
+pub struct BackgroundScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+    background: BgEnum,
+    background_visible: bool,
+}
+
+impl BackgroundScene {
+    fn from(background: BgEnum, background_visible: bool) -> BackgroundScene {
+        BackgroundScene {
+            cam: None,
+            world: None,
+            lights: None,
+            background,
+            background_visible,
+        }
+    }
+
+    pub fn sky() -> BackgroundScene {
+        BackgroundScene::from(BgEnum::Gradient(GradientBackground::sky()), true)
+    }
+
+    pub fn panorama() -> BackgroundScene {
+        // The moon map lights the balls and shows in the mirror, but the camera sees black.
+        BackgroundScene::from(BgEnum::Image(ImageBackground::from("moonmap.jpeg")), false)
+    }
+
+    pub fn checkered() -> BackgroundScene {
+        BackgroundScene::from(
+            BgEnum::Texture(TextureBackground::from(TexEnum::CheckerTexture(
+                CheckerTexture::from_color(
+                    0.25,
+                    Color::from(0.70, 0.80, 1.00),
+                    Color::from(0.9, 0.9, 0.9),
+                ),
+            ))),
+            true,
+        )
+    }
+}
+
+impl Scene for BackgroundScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+
+        let globe = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::ImageTexture(ImageTexture::from("earthmap.jpg")),
+        )));
+        let mirror = Arc::new(MatEnum::Metal(Metal::from(Color::from(0.9, 0.9, 0.9), 0.0)));
+        let glass = Arc::new(MatEnum::Dielectric(Dielectric::from(1.5)));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(-2.3, 0.0, 0.0),
+            1.0,
+            globe,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.0, 0.0, 0.0),
+            1.0,
+            mirror,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(2.3, 0.0, 0.0),
+            1.0,
+            glass,
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 1.0, 10.0);
+        let lookat = Point3::from(0.0, 0.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.background = self.background.clone();
+        cam.background_visible = self.background_visible;
+
+        let lights = HittableList::blank();
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -110,12 +110,6 @@ impl Scene for ChatScene {
 
         // Light Sources
         let mut lights = HittableList::blank();
-        lights.add(Arc::new(Quad::from(
-            Point3::from(343.0, 554.0, 332.0),
-            Vec3::from(-130.0, 0.0, 0.0),
-            Vec3::from(0.0, 0.0, -105.0),
-            default_mat.clone(),
-        )));
         lights.add(Arc::new(Sphere::from(
             Point3::from(190.0, 90.0, 190.0),
             90.0,
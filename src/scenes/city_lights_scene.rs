@@ -0,0 +1,178 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::quad::Quad;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::bvh::BvhNode;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, random_double_bounded};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct CityLightsScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl CityLightsScene {
+    pub fn blank() -> CityLightsScene {
+        CityLightsScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for CityLightsScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let mut buildings = HittableList::blank();
+        let mut windows = HittableList::blank();
+
+        let ground = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.3, 0.3, 0.32,
+        ))));
+        let concrete = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.4, 0.38, 0.35,
+        ))));
+        // Tungsten-lit and fluorescent-lit rooms.
+        let warm = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_kelvin(
+            2700.0, 4.7,
+        )));
+        let cold = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_kelvin(
+            8000.0, 3.0,
+        )));
+        let neon = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(12.0, 1.0, 6.0),
+        )));
+
+        world.add(Arc::new(Quad::from(
+            Point3::from(-400.0, 0.0, -400.0),
+            Vec3::from(800.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 800.0),
+            ground,
+        )));
+
+        // A grid of blocks, each with lit windows on all four sides. Window quads sit just off
+        // the walls with their normals pointing out so they light the street.
+        let ww = 6.0;
+        let wh = 8.0;
+        for i in 0..4 {
+            for j in 0..4 {
+                let size = 50.0;
+                let x0 = -150.0 + 80.0 * i as f64;
+                let z0 = -50.0 + 80.0 * j as f64;
+                let height = random_double_bounded(60.0, 160.0);
+                buildings.add(Arc::new(BoxPrim::from(
+                    &Point3::from(x0, 0.0, z0),
+                    &Point3::from(x0 + size, height, z0 + size),
+                    concrete.clone(),
+                )));
+
+                let rows = ((height - 10.0) / 15.0) as i64;
+                for row in 0..rows {
+                    let y = 8.0 + 15.0 * row as f64;
+                    for col in 0..4 {
+                        let s = 6.0 + 11.0 * col as f64;
+                        let faces = [
+                            (
+                                Point3::from(x0 + s, y, z0 - 0.1),
+                                Vec3::from(0.0, wh, 0.0),
+                                Vec3::from(ww, 0.0, 0.0),
+                            ),
+                            (
+                                Point3::from(x0 + s, y, z0 + size + 0.1),
+                                Vec3::from(ww, 0.0, 0.0),
+                                Vec3::from(0.0, wh, 0.0),
+                            ),
+                            (
+                                Point3::from(x0 - 0.1, y, z0 + s),
+                                Vec3::from(0.0, 0.0, ww),
+                                Vec3::from(0.0, wh, 0.0),
+                            ),
+                            (
+                                Point3::from(x0 + size + 0.1, y, z0 + s),
+                                Vec3::from(0.0, wh, 0.0),
+                                Vec3::from(0.0, 0.0, ww),
+                            ),
+                        ];
+                        for (q, u, v) in faces {
+                            if random_double() > 0.2 {
+                                continue;
+                            }
+                            let mat = if random_double() < 0.7 {
+                                warm.clone()
+                            } else {
+                                cold.clone()
+                            };
+                            windows.add(Arc::new(Quad::from(q, u, v, mat)));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Neon strips running along the street.
+        for k in 0..6 {
+            let z = -55.0 + 80.0 * k as f64 / 2.0;
+            windows.add(Arc::new(Quad::from(
+                Point3::from(-160.0, 30.0, z),
+                Vec3::from(0.0, 1.5, 0.0),
+                Vec3::from(40.0, 0.0, 0.0),
+                neon.clone(),
+            )));
+        }
+
+        world.add(Arc::new(BvhNode::from_list(&buildings)));
+        world.add(Arc::new(BvhNode::from_list(&windows)));
+
+        // The hundreds of windows are found automatically and sampled through a light
+        // hierarchy rather than one by one.
+        let lights = HittableList::blank();
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.01, 0.01, 0.03);
+
+        let vfov = 40.0;
+        let lookfrom = Point3::from(-250.0, 60.0, -250.0);
+        let lookat = Point3::from(0.0, 40.0, 50.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
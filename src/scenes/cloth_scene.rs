@@ -0,0 +1,156 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::cloth::Cloth;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::principled::constant;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct ClothScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl ClothScene {
+    pub fn blank() -> ClothScene {
+        ClothScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for ClothScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        // A gingham tablecloth, its checks woven from red and white threads.
+        let gingham = Cloth::from(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.4,
+                Color::from(0.6, 0.05, 0.05),
+                Color::from(0.8, 0.8, 0.75),
+            )),
+            TexEnum::SolidColor(SolidColor::from_rgb(0.3, 0.3, 0.3)),
+        )
+        .with_roughness(constant(0.8))
+        .with_plain_weave(80.0);
+        world.add(Arc::new(Quad::from(
+            Point3::from(-4.0, 0.0, -4.0),
+            Vec3::from(8.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 8.0),
+            Arc::new(MatEnum::Cloth(gingham)),
+        )));
+
+        let sphere = |world: &mut HittableList, row: usize, column: usize, mat: MatEnum| {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 1.1 - 1.1 * row as f64),
+                0.45,
+                Arc::new(mat),
+            )));
+        };
+
+        // Red velvet, blue woven cotton, green felt and gold silk in front.
+        let fabrics = [
+            Cloth::from_color(Color::from(0.25, 0.01, 0.03), Color::from(1.0, 0.4, 0.45))
+                .with_roughness(constant(0.3)),
+            Cloth::from_color(Color::from(0.08, 0.15, 0.45), Color::from(0.4, 0.4, 0.5))
+                .with_roughness(constant(0.8))
+                .with_plain_weave(40.0),
+            Cloth::from_color(Color::from(0.1, 0.35, 0.12), Color::from(0.3, 0.4, 0.3))
+                .with_roughness(constant(1.0)),
+            Cloth::from_color(Color::from(0.35, 0.25, 0.05), Color::from(1.0, 0.85, 0.4))
+                .with_roughness(constant(0.45))
+                .with_plain_weave(120.0),
+        ];
+        for (column, mat) in fabrics.into_iter().enumerate() {
+            sphere(&mut world, 0, column, MatEnum::Cloth(mat));
+        }
+
+        // The same colors as plain diffuse surfaces at the back, for comparison.
+        for (column, albedo) in [
+            Color::from(0.25, 0.01, 0.03),
+            Color::from(0.08, 0.15, 0.45),
+            Color::from(0.1, 0.35, 0.12),
+            Color::from(0.35, 0.25, 0.05),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            sphere(
+                &mut world,
+                1,
+                column,
+                MatEnum::Lambertian(Lambertian::from_color(albedo)),
+            );
+        }
+
+        // A softbox overhead to show off the highlights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-1.5, 5.0, -1.0),
+            Vec3::from(3.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 2.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(6.0, 6.0, 6.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::coated::Coated;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct CoatedScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl CoatedScene {
+    pub fn blank() -> CoatedScene {
+        CoatedScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for CoatedScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.5,
+                Color::from(0.05, 0.05, 0.05),
+                Color::from(0.4, 0.4, 0.4),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        let sphere = |world: &mut HittableList, row: usize, column: usize, mat: Coated| {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 1.1 - 1.1 * row as f64),
+                0.45,
+                Arc::new(MatEnum::Coated(mat)),
+            )));
+        };
+        let lambertian = |c: Color| MatEnum::Lambertian(Lambertian::from_color(c));
+
+        // Car paints in front: gloss over a flat red, over copper flakes, over a blue that is
+        // itself glossy, and a satin finish.
+        let paints = [
+            Coated::from(lambertian(Color::from(0.6, 0.03, 0.03)), 1.5, 0.0),
+            Coated::from(MatEnum::Metal(Metal::copper(0.45)), 1.5, 0.0),
+            Coated::from(
+                MatEnum::Metal(Metal::from(Color::from(0.1, 0.2, 0.6), 0.3)),
+                1.5,
+                0.0,
+            ),
+            Coated::from(lambertian(Color::from(0.05, 0.3, 0.1)), 1.5, 0.3),
+        ];
+        for (column, mat) in paints.into_iter().enumerate() {
+            sphere(&mut world, 0, column, mat);
+        }
+
+        // Pale wood under more and more coats of amber varnish at the back.
+        let wood = || {
+            MatEnum::Lambertian(Lambertian::from_texture(TexEnum::CheckerTexture(
+                CheckerTexture::from_color(
+                    0.08,
+                    Color::from(0.75, 0.55, 0.35),
+                    Color::from(0.6, 0.4, 0.22),
+                ),
+            )))
+        };
+        for (column, thickness) in [0.0, 1.0, 2.0, 4.0].iter().enumerate() {
+            let varnish = Coated::from(wood(), 1.5, 0.08)
+                .with_tint(Color::from(0.9, 0.7, 0.4))
+                .with_thickness(*thickness);
+            sphere(&mut world, 1, column, varnish);
+        }
+
+        // A softbox overhead to show off the highlights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-1.5, 5.0, -1.0),
+            Vec3::from(3.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 2.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(6.0, 6.0, 6.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
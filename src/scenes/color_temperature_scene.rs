@@ -0,0 +1,135 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::{Color, WhiteBalance};
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct ColorTemperatureScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl ColorTemperatureScene {
+    pub fn blank() -> ColorTemperatureScene {
+        ColorTemperatureScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for ColorTemperatureScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.73, 0.73, 0.73,
+        ))));
+
+        // An all-white Cornell box, so the only color comes from the lights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(555.0, 0.0, 0.0),
+            Vec3::from(0.0, 555.0, 0.0),
+            Vec3::from(0.0, 0.0, 555.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.0, 0.0, 0.0),
+            Vec3::from(0.0, 555.0, 0.0),
+            Vec3::from(0.0, 0.0, 555.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.0, 0.0, 0.0),
+            Vec3::from(555.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 555.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(555.0, 555.0, 555.0),
+            Vec3::from(-555.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, -555.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.0, 0.0, 555.0),
+            Vec3::from(555.0, 0.0, 0.0),
+            Vec3::from(0.0, 555.0, 0.0),
+            white.clone(),
+        )));
+
+        // A neutral white ceiling panel, and a candle, a tungsten bulb and a daylight bulb of
+        // equal power along the floor, each next to a white ball.
+        world.add(Arc::new(Quad::light(
+            Point3::from(343.0, 554.0, 332.0),
+            Vec3::from(-130.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, -105.0),
+            4000.0,
+            300000.0,
+        )));
+        for (i, kelvin) in [1900.0, 2700.0, 6500.0].iter().enumerate() {
+            let x = 110.0 + 167.5 * i as f64;
+            world.add(Arc::new(Sphere::light(
+                Point3::from(x, 25.0, 200.0),
+                25.0,
+                *kelvin,
+                150000.0,
+            )));
+            world.add(Arc::new(Sphere::from(
+                Point3::from(x, 60.0, 330.0),
+                60.0,
+                white.clone(),
+            )));
+        }
+
+        let aspect_ratio = 1.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 40.0;
+        let lookfrom = Point3::from(278.0, 278.0, -800.0);
+        let lookat = Point3::from(278.0, 278.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        // Balanced for indoor tungsten light, so the daylight bulb looks blue.
+        cam.white_balance = Some(WhiteBalance::from_kelvin(3200.0));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
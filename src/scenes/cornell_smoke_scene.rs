@@ -6,7 +6,7 @@ use crate::hittables::rotate_y::RotateY;
 use crate::hittables::translate::Translate;
 use crate::materials::diffuse_light::DiffuseLight;
 use crate::materials::lambertian::Lambertian;
-use crate::materials::{DefaultMat, MatEnum};
+use crate::materials::MatEnum;
 use crate::math_structures::color::Color;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::scenes::Scene;
@@ -111,14 +111,7 @@ impl Scene for CornellSmokeScene {
         )));
 
         // Light Sources
-        let mut lights = HittableList::blank();
-        let m = Arc::new(MatEnum::Default(DefaultMat {}));
-        lights.add(Arc::new(Quad::from(
-            Point3::from(343.0, 554.0, 332.0),
-            Vec3::from(-130.0, 0.0, 0.0),
-            Vec3::from(0.0, 0.0, -105.0),
-            m,
-        )));
+        let lights = HittableList::blank();
 
         let aspect_ratio = 1.0;
         let background = Color::from(0.0, 0.0, 0.0);
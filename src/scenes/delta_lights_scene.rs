@@ -0,0 +1,147 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::directional_light::DirectionalLight;
+use crate::lights::point_light::PointLight;
+use crate::lights::spot_light::SpotLight;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct DeltaLightsScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl DeltaLightsScene {
+    pub fn blank() -> DeltaLightsScene {
+        DeltaLightsScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for DeltaLightsScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let mut lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                2.0,
+                Color::from(0.2, 0.3, 0.1),
+                Color::from(0.9, 0.9, 0.9),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-50.0, 0.0, -50.0),
+            Vec3::from(100.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 100.0),
+            checker,
+        )));
+
+        let red = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.7, 0.15, 0.1,
+        ))));
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.73, 0.73, 0.73,
+        ))));
+        let steel = Arc::new(MatEnum::Metal(Metal::from(
+            Color::from(0.8, 0.8, 0.85),
+            0.05,
+        )));
+        let glass = Arc::new(MatEnum::Dielectric(Dielectric::from(1.5)));
+
+        world.add(Arc::new(Sphere::from(
+            Point3::from(-3.0, 1.0, 0.0),
+            1.0,
+            red,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.0, 1.0, 0.0),
+            1.0,
+            glass,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(3.0, 1.0, 0.0),
+            1.0,
+            steel,
+        )));
+        world.add(Arc::new(BoxPrim::from(
+            &Point3::from(-1.0, 0.0, 2.5),
+            &Point3::from(1.0, 1.2, 4.5),
+            white,
+        )));
+
+        // No emissive geometry: the scene is lit by a low sun, a warm bulb and a spot light
+        // that can only be reached through shadow rays.
+        lights.add(Arc::new(DirectionalLight::from(
+            Vec3::from(-1.0, 0.8, -0.6),
+            Color::from(0.8, 0.7, 0.6),
+            0.53,
+        )));
+        lights.add(Arc::new(PointLight::from(
+            Point3::from(-4.0, 2.5, 2.0),
+            Color::from(15.0, 10.0, 5.0),
+        )));
+        lights.add(Arc::new(SpotLight::from(
+            Point3::from(3.0, 6.0, 4.0),
+            Point3::from(0.0, 0.0, 3.5),
+            Color::from(20.0, 30.0, 60.0),
+            20.0,
+            12.0,
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.05, 0.07, 0.12);
+
+        let vfov = 35.0;
+        let lookfrom = Point3::from(0.0, 4.0, 12.0);
+        let lookat = Point3::from(0.0, 1.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -5,7 +5,7 @@ use crate::hittables::objects::sphere::Sphere;
 use crate::materials::dielectric::Dielectric;
 use crate::materials::diffuse_light::DiffuseLight;
 use crate::materials::lambertian::Lambertian;
-use crate::materials::{DefaultMat, MatEnum};
+use crate::materials::MatEnum;
 use crate::math_structures::color::Color;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::scenes::Scene;
@@ -111,13 +111,7 @@ impl Scene for EarthInABallScene {
             light,
         ));
         world.add(sun.clone());
-        let mut lights = HittableList::blank();
-        let m = Arc::new(MatEnum::Default(DefaultMat {}));
-        lights.add(Arc::new(Sphere::from(
-            Point3::from(343.0, 554.0, 332.0),
-            100.0,
-            m,
-        )));
+        let lights = HittableList::blank();
 
         let aspect_ratio = 1.0;
         let background = Color::from(0.0, 0.0, 0.0);
@@ -1,7 +1,9 @@
 use crate::camera::Camera;
 use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
 use crate::hittables::objects::quad::Quad;
 use crate::hittables::objects::sphere::Sphere;
+use crate::hittables::objects::sphere_soup::SphereSoup;
 use crate::hittables::rotate_y::RotateY;
 use crate::hittables::translate::Translate;
 use crate::materials::dielectric::Dielectric;
@@ -9,7 +11,6 @@ use crate::materials::diffuse_light::DiffuseLight;
 use crate::materials::lambertian::Lambertian;
 use crate::materials::metal::Metal;
 use crate::materials::MatEnum;
-use crate::math_structures::bvh::BvhNode;
 use crate::math_structures::color::Color;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::rtweekend::random_double_bounded;
@@ -53,11 +54,11 @@ impl Scene for FinalScene {
                 let y1 = random_double_bounded(1.0, 101.0);
                 let z1 = z0 + w;
 
-                boxes1.add(Quad::make_box(
+                boxes1.add(Arc::new(BoxPrim::from(
                     &Point3::from(x0, y0, z0),
                     &Point3::from(x1, y1, z1),
                     ground.clone(),
-                ));
+                )));
             }
         }
 
@@ -135,21 +136,16 @@ impl Scene for FinalScene {
             Arc::new(MatEnum::Lambertian(Lambertian::from_texture(pertext))),
         )));
 
-        let mut boxes2 = HittableList::blank();
         let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
             0.73, 0.73, 0.73,
         ))));
         let ns = 1000;
-        for _ in 0..ns {
-            let cir = Arc::new(Sphere::from(
-                Point3::random_bounded(0.0, 165.0),
-                10.0,
-                white.clone(),
-            ));
-            boxes2.add(cir);
-        }
+        let centers = (0..ns)
+            .map(|_| Point3::random_bounded(0.0, 165.0))
+            .collect();
+        let boxes2 = SphereSoup::from_uniform(centers, 10.0, white);
         world.add(Arc::new(Translate::from(
-            Arc::new(RotateY::from(Arc::new(BvhNode::from_list(&boxes2)), 15.0)),
+            Arc::new(RotateY::from(Arc::new(boxes2), 15.0)),
             Vec3::from(-100.0, 270.0, 395.0),
         )));
 
@@ -0,0 +1,178 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::emission_profile::EmissionProfile;
+use crate::lights::point_light::PointLight;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::{blackbody, Color};
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct FixturesScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl FixturesScene {
+    pub fn blank() -> FixturesScene {
+        FixturesScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for FixturesScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let mut lights = HittableList::blank();
+
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.75, 0.75, 0.75,
+        ))));
+        let floor = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.45, 0.42, 0.4,
+        ))));
+
+        // A gallery room open towards the camera.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-7.0, 0.0, -6.0),
+            Vec3::from(0.0, 0.0, 14.0),
+            Vec3::from(14.0, 0.0, 0.0),
+            floor,
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-7.0, 0.0, -6.0),
+            Vec3::from(14.0, 0.0, 0.0),
+            Vec3::from(0.0, 5.0, 0.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-7.0, 5.0, -6.0),
+            Vec3::from(14.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 14.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-7.0, 0.0, -6.0),
+            Vec3::from(0.0, 5.0, 0.0),
+            Vec3::from(0.0, 0.0, 14.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(7.0, 0.0, -6.0),
+            Vec3::from(0.0, 0.0, 14.0),
+            Vec3::from(0.0, 5.0, 0.0),
+            white,
+        )));
+
+        let down = Vec3::from(0.0, -1.0, 0.0);
+        let towards_wall = Vec3::from(0.0, 0.0, -1.0);
+        let warm = blackbody(3000.0);
+
+        // Left: a recessed downlight with a measured batwing distribution over a ball.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-4.0, 4.99, -1.0),
+            Vec3::from(-0.3, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, -0.3),
+            Arc::new(MatEnum::DiffuseLight(
+                DiffuseLight::from_color(&warm * 900.0).with_profile(EmissionProfile::ies(
+                    down,
+                    towards_wall,
+                    "downlight.ies",
+                )),
+            )),
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(-4.2, 0.8, -1.6),
+            0.8,
+            Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+                0.7, 0.2, 0.15,
+            )))),
+        )));
+
+        // Middle: a tight cosine-power spot from the ceiling onto a ball.
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.15, 4.99, -1.35),
+            Vec3::from(-0.3, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, -0.3),
+            Arc::new(MatEnum::DiffuseLight(
+                DiffuseLight::from_color(&warm * 900.0)
+                    .with_profile(EmissionProfile::cosine_power(down, 30.0)),
+            )),
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.0, 0.6, -1.5),
+            0.6,
+            Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+                0.2, 0.35, 0.7,
+            )))),
+        )));
+
+        // A row of wall washers grazing the back wall, and a projector throwing a map of the
+        // earth onto it from across the room.
+        for x in [-5.0, -2.5] {
+            lights.add(Arc::new(
+                PointLight::from(Point3::from(x, 4.9, -5.2), &warm * 12.0)
+                    .with_profile(EmissionProfile::ies(down, towards_wall, "wallwash.ies")),
+            ));
+        }
+        let projector = Point3::from(3.5, 2.5, 3.0);
+        lights.add(Arc::new(
+            PointLight::from(projector, Color::from(250.0, 250.0, 250.0)).with_profile(
+                EmissionProfile::projector(
+                    &Point3::from(3.5, 2.8, -6.0) - &projector,
+                    Vec3::from(0.0, 1.0, 0.0),
+                    34.0,
+                    "earthmap.jpg",
+                ),
+            ),
+        ));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 50.0;
+        let lookfrom = Point3::from(0.0, 2.5, 9.0);
+        let lookat = Point3::from(0.0, 2.2, -6.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
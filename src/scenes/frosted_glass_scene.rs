@@ -0,0 +1,116 @@
+use crate::backgrounds::gradient_background::GradientBackground;
+use crate::backgrounds::BgEnum;
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct FrostedGlassScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl FrostedGlassScene {
+    pub fn blank() -> FrostedGlassScene {
+        FrostedGlassScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for FrostedGlassScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.4,
+                Color::from(0.1, 0.1, 0.1),
+                Color::from(0.8, 0.8, 0.8),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        // A fine pattern on the wall behind, to see how much each ball blurs it.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-4.0, 0.0, -2.0),
+            Vec3::from(8.0, 0.0, 0.0),
+            Vec3::from(0.0, 4.0, 0.0),
+            Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+                TexEnum::CheckerTexture(CheckerTexture::from_color(
+                    0.15,
+                    Color::from(0.7, 0.1, 0.1),
+                    Color::from(0.9, 0.9, 0.8),
+                )),
+            ))),
+        )));
+
+        // From clear glass on the left to heavily frosted on the right.
+        for (i, roughness) in [0.0, 0.1, 0.25, 0.5].iter().enumerate() {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-2.4 + 1.6 * i as f64, 0.7, 0.5),
+                0.7,
+                Arc::new(MatEnum::Dielectric(Dielectric::rough(1.5, *roughness))),
+            )));
+        }
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 35.0;
+        let lookfrom = Point3::from(0.0, 2.0, 9.0);
+        let lookat = Point3::from(0.0, 1.2, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.background = BgEnum::Gradient(GradientBackground::sky());
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
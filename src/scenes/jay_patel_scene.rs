@@ -7,7 +7,7 @@ use crate::hittables::rotate_y::RotateY;
 use crate::materials::dielectric::Dielectric;
 use crate::materials::diffuse_light::DiffuseLight;
 use crate::materials::lambertian::Lambertian;
-use crate::materials::{DefaultMat, MatEnum};
+use crate::materials::MatEnum;
 use crate::math_structures::color::Color;
 use crate::math_structures::vec3::{Point3, Vec3};
 use crate::scenes::Scene;
@@ -103,13 +103,7 @@ impl Scene for JayPatelScene {
             light,
         ));
         world.add(sun.clone());
-        let mut lights = HittableList::blank();
-        let m = Arc::new(MatEnum::Default(DefaultMat {}));
-        lights.add(Arc::new(Sphere::from(
-            Point3::from(278.0, 555.0, 278.0),
-            100.0,
-            m,
-        )));
+        let lights = HittableList::blank();
         let aspect_ratio = 1.0;
 
         let vfov = 40.0;
@@ -0,0 +1,202 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::extrude::Extrude;
+use crate::hittables::objects::lathe::Lathe;
+use crate::hittables::objects::profile::Profile;
+use crate::hittables::objects::quad::Quad;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct LatheScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl LatheScene {
+    pub fn blank() -> LatheScene {
+        LatheScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for LatheScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                40.0,
+                Color::from(0.2, 0.3, 0.1),
+                Color::from(0.9, 0.9, 0.9),
+            )),
+        )));
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.73, 0.73, 0.73,
+        ))));
+        let terracotta = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.70, 0.35, 0.20,
+        ))));
+        let red = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.65, 0.05, 0.05,
+        ))));
+        let glass = Arc::new(MatEnum::Dielectric(Dielectric::from(1.5)));
+        let gold = Arc::new(MatEnum::Metal(Metal::from(Color::from(0.8, 0.6, 0.2), 0.1)));
+        let light = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(15.0, 15.0, 15.0),
+        )));
+
+        // Floor, back wall and light
+        world.add(Arc::new(Quad::from(
+            Point3::from(-500.0, 0.0, -500.0),
+            Vec3::from(1555.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 1555.0),
+            checker,
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-500.0, 0.0, 555.0),
+            Vec3::from(1555.0, 0.0, 0.0),
+            Vec3::from(0.0, 800.0, 0.0),
+            white,
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(178.0, 554.0, 132.0),
+            Vec3::from(200.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 200.0),
+            light,
+        )));
+
+        // Vase: smooth outer wall, over the lip and back down the inside.
+        let vase_profile = Profile::from_spline(
+            &[
+                Point3::from(60.0, 0.0, 0.0),
+                Point3::from(95.0, 60.0, 0.0),
+                Point3::from(100.0, 140.0, 0.0),
+                Point3::from(55.0, 220.0, 0.0),
+                Point3::from(45.0, 260.0, 0.0),
+                Point3::from(60.0, 290.0, 0.0),
+                Point3::from(52.0, 290.0, 0.0),
+                Point3::from(37.0, 260.0, 0.0),
+                Point3::from(47.0, 220.0, 0.0),
+                Point3::from(90.0, 140.0, 0.0),
+                Point3::from(85.0, 60.0, 0.0),
+                Point3::from(50.0, 15.0, 0.0),
+                Point3::from(0.0, 15.0, 0.0),
+            ],
+            8,
+        );
+        world.add(Arc::new(Lathe::from(
+            &vase_profile,
+            Point3::from(180.0, 0.0, 300.0),
+            Vec3::from(0.0, 1.0, 0.0),
+            terracotta,
+        )));
+
+        // Bottle: a plain polyline profile.
+        let bottle_profile = Profile::from_polyline(vec![
+            Point3::from(45.0, 0.0, 0.0),
+            Point3::from(45.0, 120.0, 0.0),
+            Point3::from(15.0, 160.0, 0.0),
+            Point3::from(15.0, 200.0, 0.0),
+        ]);
+        world.add(Arc::new(Lathe::from(
+            &bottle_profile,
+            Point3::from(380.0, 0.0, 250.0),
+            Vec3::from(0.0, 1.0, 0.0),
+            glass,
+        )));
+
+        // Extruded star logo and a rounded extruded badge.
+        let mut star = vec![];
+        for i in 0..10 {
+            let angle = std::f64::consts::PI * (0.5 + i as f64 / 5.0);
+            let radius = if i % 2 == 0 { 60.0 } else { 25.0 };
+            star.push(Point3::from(
+                radius * f64::cos(angle),
+                radius * f64::sin(angle),
+                0.0,
+            ));
+        }
+        world.add(Arc::new(Extrude::from(
+            &Profile::from_polyline(star),
+            Point3::from(300.0, 70.0, 120.0),
+            Vec3::from(1.0, 0.0, 0.0),
+            Vec3::from(0.0, 1.0, 0.0),
+            Vec3::from(0.0, 0.0, 20.0),
+            red,
+        )));
+        let badge = Profile::from_closed_spline(
+            &[
+                Point3::from(0.0, 0.0, 0.0),
+                Point3::from(80.0, 10.0, 0.0),
+                Point3::from(90.0, 60.0, 0.0),
+                Point3::from(40.0, 90.0, 0.0),
+                Point3::from(-10.0, 50.0, 0.0),
+            ],
+            8,
+        );
+        world.add(Arc::new(Extrude::from(
+            &badge,
+            Point3::from(60.0, 0.0, 100.0),
+            Vec3::from(1.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 1.0),
+            Vec3::from(0.0, 30.0, 0.0),
+            gold,
+        )));
+
+        // Light Sources
+        let lights = HittableList::blank();
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 40.0;
+        let lookfrom = Point3::from(278.0, 300.0, -600.0);
+        let lookat = Point3::from(278.0, 120.0, 250.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
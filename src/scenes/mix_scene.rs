@@ -0,0 +1,151 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::mix_material::MixMaterial;
+use crate::materials::oren_nayar::OrenNayar;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::noise_texture::NoiseTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct MixScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl MixScene {
+    pub fn blank() -> MixScene {
+        MixScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for MixScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.5,
+                Color::from(0.05, 0.05, 0.05),
+                Color::from(0.4, 0.4, 0.4),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        let sphere = |world: &mut HittableList, column: usize, mat: MixMaterial| {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 0.5),
+                0.45,
+                Arc::new(MatEnum::MixMaterial(mat)),
+            )));
+        };
+        let noise = |scale: f64| TexEnum::NoiseTexture(NoiseTexture::new(scale));
+        let checks = || {
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.15,
+                Color::from(0.0, 0.0, 0.0),
+                Color::from(1.0, 1.0, 1.0),
+            ))
+        };
+        let rust = || MatEnum::OrenNayar(OrenNayar::from_color(Color::from(0.35, 0.12, 0.04), 0.5));
+        let marble = || MatEnum::Lambertian(Lambertian::from_texture(noise(4.0)));
+        let gold_and_marble =
+            || MixMaterial::from(MatEnum::Metal(Metal::gold(0.1)), marble(), checks());
+
+        // Rusting steel, a checkerboard of gold and marble, the same checkerboard rusting, and
+        // tiles of mirror and glass.
+        let mixes = [
+            MixMaterial::from(
+                MatEnum::Metal(Metal::from(Color::from(0.6, 0.6, 0.62), 0.15)),
+                rust(),
+                noise(6.0),
+            ),
+            gold_and_marble(),
+            MixMaterial::from(MatEnum::MixMaterial(gold_and_marble()), rust(), noise(3.0)),
+            MixMaterial::from(
+                MatEnum::Metal(Metal::from(Color::from(0.9, 0.9, 0.9), 0.0)),
+                MatEnum::Dielectric(Dielectric::from(1.5)),
+                checks(),
+            ),
+        ];
+        for (column, mat) in mixes.into_iter().enumerate() {
+            sphere(&mut world, column, mat);
+        }
+
+        // A softbox overhead to show off the highlights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-1.5, 5.0, -1.0),
+            Vec3::from(3.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 2.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(6.0, 6.0, 6.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -2,18 +2,38 @@ use crate::camera::Camera;
 use crate::hittables::hittable_list::HittableList;
 use std::sync::Arc;
 
+pub mod background_scene;
 pub mod chat_scene;
+pub mod city_lights_scene;
+pub mod cloth_scene;
+pub mod coated_scene;
+pub mod color_temperature_scene;
 pub mod cornell_box_scene;
 pub mod cornell_smoke_scene;
+pub mod delta_lights_scene;
 pub mod diff_final_scene;
 pub mod earth_in_a_ball;
 pub mod earth_scene;
 pub mod final_scene;
+pub mod fixtures_scene;
+pub mod frosted_glass_scene;
 pub mod jay_patel_scene;
+pub mod lathe_scene;
+pub mod metals_scene;
+pub mod mix_scene;
+pub mod nested_dielectrics_scene;
+pub mod particle_scene;
+pub mod primitives_scene;
+pub mod principled_scene;
 pub mod purple_cornell_box_scene;
 pub mod quads_scene;
 pub mod random_spheres_scene;
+pub mod rough_diffuse_scene;
 pub mod simple_list_scene;
+pub mod studio_scene;
+pub mod subsurface_scene;
+pub mod sun_sky_scene;
+pub mod thin_film_scene;
 pub mod two_perlin_spheres_scene;
 pub mod two_spheres_scene;
 
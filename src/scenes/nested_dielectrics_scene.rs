@@ -0,0 +1,177 @@
+use crate::backgrounds::gradient_background::GradientBackground;
+use crate::backgrounds::BgEnum;
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::extrude::Extrude;
+use crate::hittables::objects::profile::Profile;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::hittables::rotate_y::RotateY;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::PI;
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct NestedDielectricsScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl NestedDielectricsScene {
+    pub fn blank() -> NestedDielectricsScene {
+        NestedDielectricsScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+fn cylinder(radius: f64, bottom: f64, height: f64, mat: Arc<MatEnum>) -> Extrude {
+    // An upright cylinder on the y axis.
+    let circle: Vec<Point3> = (0..16)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / 16.0;
+            Point3::from(radius * f64::cos(angle), radius * f64::sin(angle), 0.0)
+        })
+        .collect();
+    Extrude::from(
+        &Profile::from_closed_spline(&circle, 4),
+        Point3::from(0.0, bottom, 0.0),
+        Vec3::from(1.0, 0.0, 0.0),
+        Vec3::from(0.0, 0.0, 1.0),
+        Vec3::from(0.0, height, 0.0),
+        mat,
+    )
+}
+
+impl Scene for NestedDielectricsScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.25,
+                Color::from(0.1, 0.1, 0.1),
+                Color::from(0.8, 0.8, 0.8),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-6.0, 0.0, -2.5),
+            Vec3::from(12.0, 0.0, 0.0),
+            Vec3::from(0.0, 6.0, 0.0),
+            checker,
+        )));
+        world.add(Arc::new(Sphere::light(
+            Point3::from(-3.0, 6.0, 4.0),
+            0.8,
+            5500.0,
+            400.0,
+        )));
+
+        // A drink with ice. The tumbler is modelled as solid glass; the drink and the air
+        // above it are volumes of higher priority carved out of it, overlapping slightly so no
+        // two surfaces coincide, and the ice cubes have the highest priority of all.
+        world.add(Arc::new(cylinder(
+            1.0,
+            0.0,
+            2.4,
+            Arc::new(MatEnum::Dielectric(Dielectric::from(1.5).with_priority(1))),
+        )));
+        world.add(Arc::new(cylinder(
+            0.9,
+            1.45,
+            1.2,
+            Arc::new(MatEnum::Dielectric(Dielectric::from(1.0).with_priority(2))),
+        )));
+        world.add(Arc::new(cylinder(
+            0.9,
+            0.15,
+            1.35,
+            Arc::new(MatEnum::Dielectric(
+                Dielectric::from(1.33)
+                    .with_color(Color::from(0.9, 0.5, 0.12), 1.0)
+                    .with_priority(3),
+            )),
+        )));
+        let ice = Arc::new(MatEnum::Dielectric(Dielectric::from(1.31).with_priority(4)));
+        for (center, angle) in [
+            (Point3::from(-0.3, 1.4, 0.1), 20.0),
+            (Point3::from(0.35, 1.35, -0.2), 55.0),
+        ] {
+            world.add(Arc::new(RotateY::from(
+                Arc::new(BoxPrim::from(
+                    &Point3::from(center.x() - 0.25, center.y() - 0.2, center.z() - 0.25),
+                    &Point3::from(center.x() + 0.25, center.y() + 0.25, center.z() + 0.25),
+                    ice.clone(),
+                )),
+                angle,
+            )));
+        }
+
+        // Solid green glass, darker where it is thicker.
+        world.add(Arc::new(Sphere::from(
+            Point3::from(2.3, 0.8, 0.3),
+            0.8,
+            Arc::new(MatEnum::Dielectric(
+                Dielectric::from(1.5).with_color(Color::from(0.3, 0.75, 0.4), 1.0),
+            )),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.5, 3.5, 8.0);
+        let lookat = Point3::from(0.8, 1.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.background = BgEnum::Gradient(GradientBackground::sky());
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere_soup::SphereSoup;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::rtweekend::{random_double, random_double_bounded, PI};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct ParticleScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl ParticleScene {
+    pub fn blank() -> ParticleScene {
+        ParticleScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for ParticleScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        // Spiral star field: glowing particles whose color fades from a warm core to blue arms.
+        let n_stars = 200000;
+        let mut centers = Vec::with_capacity(n_stars);
+        let mut radii = Vec::with_capacity(n_stars);
+        let mut colors = Vec::with_capacity(n_stars);
+        for _ in 0..n_stars {
+            let arm = if random_double() < 0.5 { 0.0 } else { PI };
+            let r = 300.0 * random_double() * random_double();
+            let theta = arm + r / 60.0 + random_double_bounded(-0.4, 0.4);
+            let spread = 8.0 + 0.1 * r;
+            centers.push(Point3::from(
+                r * f64::cos(theta) + random_double_bounded(-spread, spread),
+                random_double_bounded(-spread, spread) * 0.3,
+                r * f64::sin(theta) + random_double_bounded(-spread, spread),
+            ));
+            radii.push(random_double_bounded(0.3, 0.8));
+            let f = r / 300.0;
+            colors.push(Color::from(1.0 - 0.6 * f, 0.8 - 0.3 * f, 0.5 + 0.5 * f));
+        }
+        let glow = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(1.5, 1.5, 1.5),
+        )));
+        world.add(Arc::new(SphereSoup::from(
+            centers,
+            radii,
+            Some(colors),
+            glow,
+        )));
+
+        // A droplet of fluid particles colored by height, lit from above.
+        let n_drops = 20000;
+        let mut centers = Vec::with_capacity(n_drops);
+        let mut colors = Vec::with_capacity(n_drops);
+        for _ in 0..n_drops {
+            let p = &Point3::from(0.0, 150.0, 0.0) + &(60.0 * &Vec3::random_in_unit_sphere());
+            let h = (p.y() - 90.0) / 120.0;
+            colors.push(Color::from(0.1 + 0.8 * h, 0.3, 0.9 - 0.8 * h));
+            centers.push(p);
+        }
+        let radii = vec![2.5; n_drops];
+        let fluid = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.9, 0.9, 0.9,
+        ))));
+        world.add(Arc::new(SphereSoup::from(
+            centers,
+            radii,
+            Some(colors),
+            fluid,
+        )));
+
+        let light = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(8.0, 8.0, 8.0),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-60.0, 300.0, -60.0),
+            Vec3::from(120.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 120.0),
+            light,
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 50.0;
+        let lookfrom = Point3::from(0.0, 350.0, -450.0);
+        let lookat = Point3::from(0.0, 40.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -0,0 +1,201 @@
+use crate::camera::Camera;
+use crate::hittables::exclude_from_lights::ExcludeFromLights;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::planar::Planar;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::hittables::rotate_y::RotateY;
+use crate::hittables::translate::Translate;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct PrimitivesScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl PrimitivesScene {
+    pub fn blank() -> PrimitivesScene {
+        PrimitivesScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for PrimitivesScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.73, 0.73, 0.73,
+        ))));
+        let red = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.65, 0.05, 0.05,
+        ))));
+        let blue = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.05, 0.15, 0.65,
+        ))));
+        let green = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.12, 0.45, 0.15,
+        ))));
+        let yellow = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.8, 0.7, 0.1,
+        ))));
+        let light = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(10.0, 9.0, 7.0),
+        )));
+        let ball_light = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(12.0, 6.0, 3.0),
+        )));
+        let disk_light = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(6.0, 6.0, 8.0),
+        )));
+
+        // Floor and back wall
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.0, 0.0, 0.0),
+            Vec3::from(555.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 555.0),
+            white.clone(),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(0.0, 0.0, 555.0),
+            Vec3::from(555.0, 0.0, 0.0),
+            Vec3::from(0.0, 555.0, 0.0),
+            white.clone(),
+        )));
+
+        // Oriented box with a red lid and blue front.
+        let angle = 0.5_f64;
+        let mut crate_box = BoxPrim::from_oriented(
+            Point3::from(170.0, 60.0, 300.0),
+            [
+                Vec3::from(150.0 * f64::cos(angle), 0.0, 150.0 * f64::sin(angle)),
+                Vec3::from(0.0, 120.0, 0.0),
+                Vec3::from(-100.0 * f64::sin(angle), 0.0, 100.0 * f64::cos(angle)),
+            ],
+            white,
+        );
+        crate_box.set_face_material(3, red);
+        crate_box.set_face_material(4, blue.clone());
+        world.add(Arc::new(crate_box));
+
+        // Glowing cube used as the area light, placed through the instancing wrappers; it is
+        // still found and sampled as a light through them.
+        let a = Point3::from(-50.0, -50.0, -50.0);
+        let b = Point3::from(50.0, 50.0, 50.0);
+        let offset = Vec3::from(380.0, 250.0, 300.0);
+        world.add(Arc::new(Translate::from(
+            Arc::new(RotateY::from(Arc::new(BoxPrim::from(&a, &b, light)), 30.0)),
+            offset,
+        )));
+
+        // Moving glowing ball, sampled where it is at each ray's time.
+        let c1 = Point3::from(90.0, 300.0, 200.0);
+        let c2 = Point3::from(160.0, 300.0, 200.0);
+        world.add(Arc::new(Sphere::from_moving(c1, c2, 25.0, ball_light)));
+
+        // Planar shapes on the back wall
+        world.add(Arc::new(Planar::triangle(
+            Point3::from(450.0, 350.0, 554.0),
+            Vec3::from(80.0, 0.0, 0.0),
+            Vec3::from(40.0, 80.0, 0.0),
+            green.clone(),
+        )));
+        world.add(Arc::new(Planar::ellipse(
+            Point3::from(300.0, 420.0, 554.0),
+            Vec3::from(-60.0, 0.0, 0.0),
+            Vec3::from(0.0, 30.0, 0.0),
+            yellow,
+        )));
+        world.add(Arc::new(Planar::annulus(
+            Point3::from(150.0, 400.0, 554.0),
+            Vec3::from(0.0, 0.0, -1.0),
+            25.0,
+            50.0,
+            green,
+        )));
+        world.add(Arc::new(Planar::polygon(
+            &[
+                Point3::from(80.0, 1.0, 80.0),
+                Point3::from(80.0, 1.0, 200.0),
+                Point3::from(140.0, 1.0, 200.0),
+                Point3::from(140.0, 1.0, 130.0),
+                Point3::from(220.0, 1.0, 130.0),
+                Point3::from(220.0, 1.0, 80.0),
+            ],
+            blue.clone(),
+        )));
+
+        // Disk light in the ceiling
+        let disk_center = Point3::from(278.0, 554.0, 278.0);
+        world.add(Arc::new(Planar::disk(
+            disk_center,
+            Vec3::from(0.0, -1.0, 0.0),
+            80.0,
+            disk_light,
+        )));
+
+        // A string of dim lamps along the back wall, kept out of light sampling.
+        let lamp = Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+            Color::from(3.0, 2.5, 1.5),
+        )));
+        let mut lamps = HittableList::blank();
+        for i in 0..24 {
+            let x = 40.0 + 20.0 * i as f64;
+            let center = Point3::from(x, 520.0 - 30.0 * f64::sin(0.25 * i as f64), 540.0);
+            lamps.add(Arc::new(Sphere::from(center, 4.0, lamp.clone())));
+        }
+        world.add(Arc::new(ExcludeFromLights::from(Arc::new(lamps))));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.05, 0.05, 0.08);
+
+        let vfov = 40.0;
+        let lookfrom = Point3::from(278.0, 278.0, -600.0);
+        let lookat = Point3::from(278.0, 150.0, 300.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
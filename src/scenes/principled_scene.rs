@@ -0,0 +1,177 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::principled::{constant, Principled};
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct PrincipledScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl PrincipledScene {
+    pub fn blank() -> PrincipledScene {
+        PrincipledScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for PrincipledScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.5,
+                Color::from(0.05, 0.05, 0.05),
+                Color::from(0.4, 0.4, 0.4),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        let sphere = |world: &mut HittableList, row: usize, column: usize, mat: Principled| {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 1.65 - 1.1 * row as f64),
+                0.45,
+                Arc::new(MatEnum::Principled(mat)),
+            )));
+        };
+        let orange = Color::from(0.9, 0.35, 0.1);
+
+        // From front to back: plastic turning into metal from left to right, plastic getting
+        // rougher, the coat, sheen and transmission lobes, and parameters driven by textures.
+        for (column, metallic) in [0.0, 0.33, 0.67, 1.0].iter().enumerate() {
+            let mat = Principled::from_color(orange)
+                .with_metallic(constant(*metallic))
+                .with_roughness(constant(0.25));
+            sphere(&mut world, 0, column, mat);
+        }
+        for (column, roughness) in [0.0, 0.2, 0.5, 1.0].iter().enumerate() {
+            let mat = Principled::from_color(Color::from(0.1, 0.25, 0.7))
+                .with_roughness(constant(*roughness));
+            sphere(&mut world, 1, column, mat);
+        }
+
+        let car_paint = Principled::from_color(Color::from(0.6, 0.02, 0.05))
+            .with_roughness(constant(0.6))
+            .with_clearcoat(constant(1.0));
+        let velvet = Principled::from_color(Color::from(0.3, 0.05, 0.25))
+            .with_roughness(constant(1.0))
+            .with_sheen(constant(1.0));
+        let glass = Principled::from_color(Color::from(1.0, 1.0, 1.0))
+            .with_roughness(constant(0.0))
+            .with_transmission(constant(1.0));
+        let frosted = Principled::from_color(Color::from(0.7, 0.9, 0.75))
+            .with_roughness(constant(0.3))
+            .with_transmission(constant(1.0));
+        for (column, mat) in [car_paint, velvet, glass, frosted].into_iter().enumerate() {
+            sphere(&mut world, 2, column, mat);
+        }
+
+        let checker = |scale: f64, a: f64, b: f64| {
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                scale,
+                Color::from(a, a, a),
+                Color::from(b, b, b),
+            ))
+        };
+        let patchy_gold = Principled::from_color(Color::from(1.0, 0.76, 0.33))
+            .with_metallic(constant(1.0))
+            .with_roughness(checker(0.15, 0.05, 0.5));
+        let inlaid = Principled::from_color(orange)
+            .with_metallic(checker(0.15, 0.0, 1.0))
+            .with_roughness(constant(0.2));
+        let tinted = Principled::from_color(Color::from(0.2, 0.6, 0.2))
+            .with_roughness(constant(0.3))
+            .with_specular(constant(1.0))
+            .with_specular_tint(constant(1.0));
+        let glowing = Principled::from_color(Color::from(0.05, 0.05, 0.05))
+            .with_roughness(constant(0.2))
+            .with_emission(TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.1,
+                Color::from(4.0, 2.0, 0.5),
+                Color::from(0.0, 0.0, 0.0),
+            )));
+        for (column, mat) in [patchy_gold, inlaid, tinted, glowing]
+            .into_iter()
+            .enumerate()
+        {
+            sphere(&mut world, 3, column, mat);
+        }
+
+        // A softbox overhead to show off the highlights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-1.5, 5.0, -1.0),
+            Vec3::from(3.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 2.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(6.0, 6.0, 6.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
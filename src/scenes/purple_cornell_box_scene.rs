@@ -110,13 +110,6 @@ impl Scene for PurpleCornellBoxScene {
         // Light Sources
         let mut lights = HittableList::blank();
         let m = Arc::new(MatEnum::Default(DefaultMat {}));
-        lights.add(Arc::new(Quad::from(
-            Point3::from(343.0, 554.0, 332.0),
-            Vec3::from(-130.0, 0.0, 0.0),
-            Vec3::from(0.0, 0.0, -105.0),
-            m.clone(),
-        )));
-
         lights.add(Arc::new(Sphere::from(
             Point3::from(190.0, 90.0, 190.0),
             90.0,
@@ -0,0 +1,126 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::directional_light::DirectionalLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::oren_nayar::OrenNayar;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::solid_color::SolidColor;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct RoughDiffuseScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl RoughDiffuseScene {
+    pub fn blank() -> RoughDiffuseScene {
+        RoughDiffuseScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for RoughDiffuseScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let mut lights = HittableList::blank();
+
+        // Two moons lit from almost behind the camera: the Lambertian one on the left darkens
+        // towards its rim, the rough one on the right stays flat like the full moon.
+        let moon = || TexEnum::ImageTexture(ImageTexture::from("moonmap.jpeg"));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(-1.1, 0.75, 0.0),
+            0.95,
+            Arc::new(MatEnum::Lambertian(Lambertian::from_texture(moon()))),
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(1.1, 0.75, 0.0),
+            0.95,
+            Arc::new(MatEnum::OrenNayar(OrenNayar::from_texture(
+                moon(),
+                TexEnum::SolidColor(SolidColor::from_rgb(0.6, 0.6, 0.6)),
+            ))),
+        )));
+
+        // Clay getting rougher from left to right, and a last ball with smooth and rough
+        // squares.
+        let clay = Color::from(0.75, 0.45, 0.3);
+        let rough_squares = OrenNayar::from_texture(
+            TexEnum::SolidColor(SolidColor::from(clay)),
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.15,
+                Color::from(0.0, 0.0, 0.0),
+                Color::from(1.0, 1.0, 1.0),
+            )),
+        );
+        let clays = [
+            OrenNayar::from_color(clay, 0.0),
+            OrenNayar::from_color(clay, 0.3),
+            OrenNayar::from_color(clay, 1.0),
+            rough_squares,
+        ];
+        for (column, mat) in clays.into_iter().enumerate() {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, -1.0, 0.0),
+                0.45,
+                Arc::new(MatEnum::OrenNayar(mat)),
+            )));
+        }
+
+        lights.add(Arc::new(DirectionalLight::from(
+            Vec3::from(0.25, 0.15, 1.0),
+            Color::from(3.0, 3.0, 3.0),
+            0.53,
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 22.0;
+        let lookfrom = Point3::from(0.0, 0.0, 12.0);
+        let lookat = Point3::from(0.0, 0.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
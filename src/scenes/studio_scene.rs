@@ -0,0 +1,122 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::planar::Planar;
+use crate::hittables::objects::sphere::Sphere;
+use crate::hittables::rotate_y::RotateY;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct StudioScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl StudioScene {
+    pub fn blank() -> StudioScene {
+        StudioScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for StudioScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        // Product shot on a white turntable, lit only by a studio HDRI.
+        let white = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.8, 0.8, 0.8,
+        ))));
+        world.add(Arc::new(Planar::disk(
+            Point3::from(0.0, 0.0, 0.0),
+            Vec3::from(0.0, 1.0, 0.0),
+            6.0,
+            white,
+        )));
+
+        let gold = Arc::new(MatEnum::Metal(Metal::from(
+            Color::from(1.0, 0.78, 0.34),
+            0.1,
+        )));
+        let glass = Arc::new(MatEnum::Dielectric(Dielectric::from(1.5)));
+        let blue = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.1, 0.2, 0.6,
+        ))));
+
+        world.add(Arc::new(Sphere::from(
+            Point3::from(-1.6, 1.0, 0.0),
+            1.0,
+            gold,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.8, 0.7, 1.2),
+            0.7,
+            glass,
+        )));
+        world.add(Arc::new(RotateY::from(
+            Arc::new(BoxPrim::from(
+                &Point3::from(0.5, 0.0, -1.5),
+                &Point3::from(2.0, 1.5, 0.0),
+                blue,
+            )),
+            20.0,
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 4.0, 10.0);
+        let lookat = Point3::from(0.0, 0.8, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -0,0 +1,128 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct SubsurfaceScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl SubsurfaceScene {
+    pub fn blank() -> SubsurfaceScene {
+        SubsurfaceScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for SubsurfaceScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.5,
+                Color::from(0.05, 0.05, 0.05),
+                Color::from(0.4, 0.4, 0.4),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        // Marble, skin, milk and ketchup, with the scene's units taken as centimetres, and a
+        // forward scattering amber ball at the back. The surfaces are smooth, since light
+        // sampled through a rough surface is mostly wasted on every step out of the walk.
+        let presets: [fn(f64) -> Dielectric; 4] = [
+            Dielectric::marble,
+            Dielectric::skin,
+            Dielectric::whole_milk,
+            Dielectric::ketchup,
+        ];
+        for (column, preset) in presets.iter().enumerate() {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 0.5),
+                0.45,
+                Arc::new(MatEnum::Dielectric(preset(10.0))),
+            )));
+        }
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.0, 0.6, -1.2),
+            0.6,
+            Arc::new(MatEnum::Dielectric(
+                Dielectric::from(1.3)
+                    .with_scattering(Color::from(4.0, 3.0, 2.0))
+                    .with_absorption(Color::from(0.02, 0.2, 0.6))
+                    .with_anisotropy(0.6),
+            )),
+        )));
+
+        // A large softbox behind and above, so light shines through the edges.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-2.0, 2.5, -4.0),
+            Vec3::from(4.0, 0.0, 0.0),
+            Vec3::from(0.0, 2.5, 1.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(5.0, 5.0, 5.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.5, 0.55, 0.6);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
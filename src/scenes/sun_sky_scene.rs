@@ -0,0 +1,128 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::box_prim::BoxPrim;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::sky::PreethamSky;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use std::sync::Arc;
+
+pub struct SunSkyScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl SunSkyScene {
+    pub fn blank() -> SunSkyScene {
+        SunSkyScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for SunSkyScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let grass = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.2, 0.3, 0.12,
+        ))));
+        let concrete = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.6, 0.58, 0.55,
+        ))));
+        let plaster = Arc::new(MatEnum::Lambertian(Lambertian::from_color(Color::from(
+            0.85, 0.82, 0.78,
+        ))));
+        let glass = Arc::new(MatEnum::Dielectric(Dielectric::from(1.5)));
+
+        world.add(Arc::new(Quad::from(
+            Point3::from(-500.0, 0.0, -500.0),
+            Vec3::from(1000.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 1000.0),
+            grass,
+        )));
+
+        // A pavilion: a raised slab, four columns and a flat roof throwing a long shadow, with
+        // a solid wall behind.
+        world.add(Arc::new(BoxPrim::from(
+            &Point3::from(-8.0, 0.0, -6.0),
+            &Point3::from(8.0, 0.5, 6.0),
+            concrete.clone(),
+        )));
+        for (x, z) in [(-6.5, -4.5), (6.5, -4.5), (-6.5, 4.5), (6.5, 4.5)] {
+            world.add(Arc::new(BoxPrim::from(
+                &Point3::from(x - 0.4, 0.5, z - 0.4),
+                &Point3::from(x + 0.4, 5.5, z + 0.4),
+                plaster.clone(),
+            )));
+        }
+        world.add(Arc::new(BoxPrim::from(
+            &Point3::from(-8.5, 5.5, -6.5),
+            &Point3::from(8.5, 6.2, 6.5),
+            plaster.clone(),
+        )));
+        world.add(Arc::new(BoxPrim::from(
+            &Point3::from(-4.0, 0.5, -5.5),
+            &Point3::from(4.0, 5.5, -5.0),
+            concrete,
+        )));
+        world.add(Arc::new(Sphere::from(
+            Point3::from(0.0, 1.7, 0.0),
+            1.2,
+            glass,
+        )));
+
+        // Late afternoon sun low in the west on a clear day.
+        let sky = PreethamSky::from(20.0, 250.0, 3.0);
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 50.0;
+        let lookfrom = Point3::from(10.0, 3.0, 20.0);
+        let lookat = Point3::from(0.0, 3.0, 0.0);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(sky.environment(512, 256, 0.05)));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::camera::Camera;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::objects::quad::Quad;
+use crate::hittables::objects::sphere::Sphere;
+use crate::lights::environment_light::EnvironmentLight;
+use crate::materials::dielectric::Dielectric;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::metal::Metal;
+use crate::materials::thin_film::ThinFilm;
+use crate::materials::MatEnum;
+use crate::math_structures::color::Color;
+use crate::math_structures::vec3::{Point3, Vec3};
+use crate::scenes::Scene;
+use crate::textures::checker_texture::CheckerTexture;
+use crate::textures::noise_texture::NoiseTexture;
+use crate::textures::TexEnum;
+use std::sync::Arc;
+
+pub struct ThinFilmScene {
+    pub(crate) cam: Option<Arc<Camera>>,
+    pub(crate) world: Option<Arc<HittableList>>,
+    pub(crate) lights: Option<Arc<HittableList>>,
+}
+
+impl ThinFilmScene {
+    pub fn blank() -> ThinFilmScene {
+        ThinFilmScene {
+            cam: None,
+            world: None,
+            lights: None,
+        }
+    }
+}
+
+impl Scene for ThinFilmScene {
+    fn generate_scene(&mut self, image_width: i64, samples_per_pixel: i64, max_depth: i64) {
+        let mut world = HittableList::blank();
+        let lights = HittableList::blank();
+
+        let checker = Arc::new(MatEnum::Lambertian(Lambertian::from_texture(
+            TexEnum::CheckerTexture(CheckerTexture::from_color(
+                0.5,
+                Color::from(0.05, 0.05, 0.05),
+                Color::from(0.4, 0.4, 0.4),
+            )),
+        )));
+        world.add(Arc::new(Quad::from(
+            Point3::from(-20.0, 0.0, -20.0),
+            Vec3::from(40.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 40.0),
+            checker,
+        )));
+
+        let sphere = |world: &mut HittableList, row: usize, column: usize, mat: MatEnum| {
+            world.add(Arc::new(Sphere::from(
+                Point3::from(-1.65 + 1.1 * column as f64, 0.45, 1.1 - 1.1 * row as f64),
+                0.45,
+                Arc::new(mat),
+            )));
+        };
+        let swirls = |scale: f64| TexEnum::NoiseTexture(NoiseTexture::new(scale));
+
+        // Soap bubbles, a drop of water with a film of oil on it and rough glass with a film
+        // in front. The noise stands in for the film draining and swirling.
+        let films = [
+            Dielectric::from(1.0).with_thin_film(ThinFilm::from_texture(
+                1.33,
+                swirls(4.0),
+                250.0,
+                700.0,
+            )),
+            Dielectric::from(1.0).with_thin_film(ThinFilm::from_texture(
+                1.33,
+                swirls(1.5),
+                100.0,
+                400.0,
+            )),
+            Dielectric::from(1.33).with_thin_film(ThinFilm::from_texture(
+                1.47,
+                swirls(3.0),
+                200.0,
+                900.0,
+            )),
+            Dielectric::rough(1.5, 0.2).with_thin_film(ThinFilm::from(2.0, 350.0)),
+        ];
+        for (column, mat) in films.into_iter().enumerate() {
+            sphere(&mut world, 0, column, MatEnum::Dielectric(mat));
+        }
+
+        // Steel heated until its oxide layer grows thicker and thicker at the back.
+        for (column, thickness) in [40.0, 90.0, 150.0, 250.0].iter().enumerate() {
+            let steel = Metal::conductor(
+                Color::from(2.9, 2.9, 2.6),
+                Color::from(3.1, 2.9, 2.8),
+                0.1,
+                0.0,
+                Vec3::from(0.0, 1.0, 0.0),
+            )
+            .with_thin_film(ThinFilm::from(2.2, *thickness));
+            sphere(&mut world, 1, column, MatEnum::Metal(steel));
+        }
+
+        // A softbox overhead to show off the highlights.
+        world.add(Arc::new(Quad::from(
+            Point3::from(-1.5, 5.0, -1.0),
+            Vec3::from(3.0, 0.0, 0.0),
+            Vec3::from(0.0, 0.0, 2.0),
+            Arc::new(MatEnum::DiffuseLight(DiffuseLight::from_color(
+                Color::from(6.0, 6.0, 6.0),
+            ))),
+        )));
+
+        let aspect_ratio = 16.0 / 9.0;
+        let background = Color::from(0.0, 0.0, 0.0);
+
+        let vfov = 30.0;
+        let lookfrom = Point3::from(0.0, 5.5, 9.0);
+        let lookat = Point3::from(0.0, 0.2, -0.4);
+        let vup = Vec3::from(0.0, 1.0, 0.0);
+
+        let defocus_angle = 0.0;
+
+        let mut cam = Camera::initialize(
+            aspect_ratio,
+            image_width,
+            samples_per_pixel,
+            max_depth,
+            vfov,
+            lookfrom,
+            lookat,
+            vup,
+            defocus_angle,
+            10.0,
+            background,
+        );
+        cam.environment = Some(Arc::new(EnvironmentLight::from_image(
+            "studio.hdr",
+            1.0,
+            0.0,
+        )));
+
+        self.cam = Some(Arc::new(cam));
+        self.world = Some(Arc::new(world));
+        self.lights = Some(Arc::new(lights));
+    }
+
+    fn get_cam(&self) -> Arc<Camera> {
+        <Option<Arc<Camera>> as Clone>::clone(&self.cam).unwrap()
+    }
+
+    fn get_world(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.world).unwrap()
+    }
+
+    fn get_lights(&self) -> Arc<HittableList> {
+        <Option<Arc<HittableList>> as Clone>::clone(&self.lights).unwrap()
+    }
+}
@@ -15,6 +15,12 @@ impl ImageTexture {
             image: RtwImage::from_image(filename),
         }
     }
+    pub fn aspect_ratio(&self) -> f64 {
+        if self.image.height() <= 0 {
+            return 1.0;
+        }
+        self.image.width() as f64 / self.image.height() as f64
+    }
 }
 
 impl Texture for ImageTexture {
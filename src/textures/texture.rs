@@ -8,3 +8,21 @@ use crate::math_structures::vec3::Point3;
 pub trait Texture {
     fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
 }
+
+pub fn average(texture: &impl Texture) -> Color {
+    // The texture's mean over a spread of points, for when it matters as a whole, such as how
+    // bright a textured emitter is. Solid textures are sampled in a cube of side 2 about the
+    // origin. Steps by irrational fractions spread the points evenly along every coordinate.
+    const SAMPLES: usize = 64;
+    let mut sum = Color::blank();
+    for i in 0..SAMPLES {
+        let at = |step: f64| (0.5 + i as f64 * step).fract();
+        let p = Point3::from(
+            2.0 * at(0.4301597090) - 1.0,
+            2.0 * at(0.2451223338) - 1.0,
+            2.0 * at(0.8566748839) - 1.0,
+        );
+        sum += &texture.value(at(0.7548776662), at(0.5698402910), &p);
+    }
+    &sum / SAMPLES as f64
+}
@@ -15,7 +15,7 @@ use crate::textures::TexEnum;
 #[derive(Clone)]
 pub struct ConstantMedium {
     boundary: Arc<dyn Hittable + Send + Sync>,
-    neg_inv_density: f64,
+    density: f64,
     phase_function: MatEnum,
 }
 
@@ -25,7 +25,7 @@ impl ConstantMedium {
         let phase_function = MatEnum::Isotropic(Isotropic::from_texture(a));
         ConstantMedium {
             boundary: b,
-            neg_inv_density: -1.0 / d,
+            density: d,
             phase_function,
         }
     }
@@ -34,12 +34,18 @@ impl ConstantMedium {
         let phase_function = MatEnum::Isotropic(Isotropic::from_color(c));
         ConstantMedium {
             boundary: b,
-            neg_inv_density: -1.0 / d,
+            density: d,
             phase_function,
         }
     }
 }
 
+pub fn sample_distance(density: f64) -> f64 {
+    // How far light travels through a medium of the given density before it interacts with a
+    // particle, drawn from the exponential distribution density * exp(-density * distance).
+    -f64::ln(random_double()) / density
+}
+
 impl Hittable for ConstantMedium {
     fn hit(&self, r: &Ray, ray_t: &Interval) -> Option<HitRecord> {
         let mut rec1;
@@ -80,7 +86,7 @@ impl Hittable for ConstantMedium {
 
         let ray_length = r.direction().length();
         let distance_inside_boundary = (rec2.t - rec1.t) * ray_length;
-        let hit_distance = self.neg_inv_density * f64::ln(random_double());
+        let hit_distance = sample_distance(self.density);
 
         if hit_distance > distance_inside_boundary {
             return None;
@@ -96,6 +102,7 @@ impl Hittable for ConstantMedium {
             front_face: true, // also arbitrary
             u: 0.0,
             v: 0.0,
+            color: None,
         };
         Some(rec)
     }
@@ -107,4 +114,9 @@ impl Hittable for ConstantMedium {
     fn clone_dyn(&self) -> Box<dyn Hittable + Send + Sync> {
         Box::new((*self).clone())
     }
+
+    fn lights(&self) -> Vec<Arc<dyn Hittable + Send + Sync>> {
+        // Glowing volumes can't be sampled as lights; they are only found by chance.
+        vec![]
+    }
 }